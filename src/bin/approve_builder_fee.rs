@@ -1,6 +1,5 @@
-use alloy_primitives::{Address, U256};
-use alloy_signer_local::LocalWallet;
-use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, BuilderInfo};
+use alloy_primitives::Address;
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, InfoClient, LocalWallet};
 use log::info;
 
 #[tokio::main]
@@ -9,13 +8,23 @@ async fn main() {
     // Key was randomly generated for testing and shouldn't be used with any real funds
     let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
     let wallet = priv_key.parse::<LocalWallet>().unwrap();
+    let user = wallet.address();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
+    let info_client = InfoClient::new(BaseUrl::Testnet.get_url());
 
-    let builder = BuilderInfo {
-        builder: "0x1ab189B7801140900C711E458212F9c76F8dAC79".to_string(),
-        fee: 1,
-    };
+    let builder = "0x1ab189B7801140900C711E458212F9c76F8dAC79"
+        .parse::<Address>()
+        .unwrap();
 
-    info!("Builder info: {:?}", builder);
+    let response = exchange_client
+        .approve_builder_fee(builder, "0.01%".to_string())
+        .await
+        .unwrap();
+    info!("Approve builder fee response: {response:?}");
+
+    let max_fee = info_client.max_builder_fee(user, builder).await.unwrap();
+    info!("Max builder fee approved for {builder}: {max_fee} tenths of a basis point");
 }
@@ -0,0 +1,20 @@
+use chrono::Utc;
+use hyperliquid_rust_sdk::{bridge::BridgeClient, Chain, LocalWallet};
+use log::info;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    // e.g. `anvil --fork-url https://sepolia-rollup.arbitrum.io/rpc --auto-impersonate`
+    let bridge_client = BridgeClient::new("http://localhost:8545".to_string(), Chain::Testnet);
+
+    let usd = 5_000_000; // 5 USDC
+    let deadline = Utc::now().timestamp() as u64 + 3600;
+    let tx_hash = bridge_client.deposit(&wallet, usd, deadline).await.unwrap();
+    info!("Deposit transaction: {tx_hash}");
+}
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, U256};
+use alloy_primitives::Address;
 use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, LocalWallet};
 use log::info;
 
@@ -10,17 +10,18 @@ async fn main() {
         .parse()
         .unwrap();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
     let amount = "5"; // 5 USD
     let destination = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414";
 
-    let amount = amount.parse::<U256>().unwrap();
     let destination = destination.parse::<Address>().unwrap();
 
-    exchange_client
-        .withdraw(destination, amount, "Testnet".to_string())
+    let status = exchange_client
+        .withdraw(destination, amount)
         .await
         .unwrap();
-    info!("Withdraw completed");
+    info!("Withdraw status: {status:?}");
 }
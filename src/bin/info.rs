@@ -1,5 +1,5 @@
 use alloy_primitives::Address;
-use hyperliquid_rust_sdk::{BaseUrl, InfoClient};
+use hyperliquid_rust_sdk::{BaseUrl, CandleInterval, InfoClient, L2BookOptions};
 use log::info;
 
 const ADDRESS: &str = "0xc64cc00b46101bd40aa1c3121195e85c0b0918d8";
@@ -10,22 +10,22 @@ async fn main() {
     let info_client = InfoClient::new(BaseUrl::Testnet.get_url());
     let user = "0xc64cc00b46101bd40aa1c3121195e85c0b0918d8".parse::<Address>().unwrap();
 
-    let user_state = info_client.user_state(user).await.unwrap();
+    let user_state = info_client.user_state(user, None).await.unwrap();
     info!("User state: {user_state:?}");
 
     let user_fills = info_client.user_fills(user).await.unwrap();
     info!("User fills: {user_fills:?}");
 
-    let open_orders = info_client.open_orders(user).await.unwrap();
+    let open_orders = info_client.open_orders(user, None).await.unwrap();
     info!("Open orders: {open_orders:?}");
 
-    let meta = info_client.get_meta().await.unwrap();
+    let meta = info_client.get_meta(None).await.unwrap();
     info!("Meta: {meta:?}");
 
-    let all_mids = info_client.all_mids().await.unwrap();
+    let all_mids = info_client.all_mids(None).await.unwrap();
     info!("All mids: {all_mids:?}");
 
-    let l2_snapshot = info_client.l2_snapshot("ETH".to_string()).await.unwrap();
+    let l2_snapshot = info_client.l2_snapshot("ETH".to_string(), L2BookOptions::default()).await.unwrap();
     info!("L2 snapshot: {l2_snapshot:?}");
 
     let recent_trades = info_client.recent_trades("ETH".to_string()).await.unwrap();
@@ -34,7 +34,7 @@ async fn main() {
     let candles_snapshot = info_client
         .candles_snapshot(
             "ETH".to_string(),
-            "1m".to_string(),
+            CandleInterval::OneMinute,
             1704067200,
             1704153600,
         )
@@ -61,7 +61,7 @@ async fn open_orders_example(info_client: &InfoClient) {
 
     info!(
         "Open order data for {user}: {:?}",
-        info_client.open_orders(user).await.unwrap()
+        info_client.open_orders(user, None).await.unwrap()
     );
 }
 
@@ -70,7 +70,7 @@ async fn user_state_example(info_client: &InfoClient) {
 
     info!(
         "User state data for {user}: {:?}",
-        info_client.user_state(user).await.unwrap()
+        info_client.user_state(user, None).await.unwrap()
     );
 }
 
@@ -111,11 +111,11 @@ async fn recent_trades(info_client: &InfoClient) {
 }
 
 async fn meta_example(info_client: &InfoClient) {
-    info!("Metadata: {:?}", info_client.get_meta().await.unwrap());
+    info!("Metadata: {:?}", info_client.get_meta(None).await.unwrap());
 }
 
 async fn all_mids_example(info_client: &InfoClient) {
-    info!("All mids: {:?}", info_client.all_mids().await.unwrap());
+    info!("All mids: {:?}", info_client.all_mids(None).await.unwrap());
 }
 
 async fn user_fills_example(info_client: &InfoClient) {
@@ -143,7 +143,7 @@ async fn l2_snapshot_example(info_client: &InfoClient) {
 
     info!(
         "L2 snapshot data for {coin}: {:?}",
-        info_client.l2_snapshot(coin.to_string()).await.unwrap()
+        info_client.l2_snapshot(coin.to_string(), L2BookOptions::sig_figs(3)).await.unwrap()
     );
 }
 
@@ -151,12 +151,12 @@ async fn candles_snapshot_example(info_client: &InfoClient) {
     let coin = "ETH";
     let start_timestamp = 1690540602225;
     let end_timestamp = 1690569402225;
-    let interval = "1h";
+    let interval = CandleInterval::OneHour;
 
     info!(
         "Candles snapshot data for {coin} between timestamps {start_timestamp} and {end_timestamp} with interval {interval}: {:?}",
         info_client
-            .candles_snapshot(coin.to_string(), interval.to_string(), start_timestamp, end_timestamp)
+            .candles_snapshot(coin.to_string(), interval, start_timestamp, end_timestamp)
             .await
             .unwrap()
     );
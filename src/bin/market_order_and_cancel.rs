@@ -14,7 +14,9 @@ async fn main() {
     let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
     let wallet = priv_key.parse::<PrivateKeySigner>().unwrap();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
     // Open position with a limit order
     let order = ClientOrderRequest {
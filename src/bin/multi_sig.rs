@@ -0,0 +1,46 @@
+use alloy_primitives::Address;
+use hyperliquid_rust_sdk::{
+    Actions, BaseUrl, ExchangeClient, LocalWallet, MultiSigTransaction, UpdateLeverage,
+};
+use log::info;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Keys were randomly generated for testing and shouldn't be used with any real funds
+    let outer_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+    let other_key = "0123456789012345678901234567890123456789012345678901234567890123";
+    let outer_wallet = outer_key.parse::<LocalWallet>().unwrap();
+    let other_wallet = other_key.parse::<LocalWallet>().unwrap();
+
+    let exchange_client =
+        ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), outer_wallet.clone(), None)
+            .await
+            .unwrap();
+
+    let multi_sig_user = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+        .parse::<Address>()
+        .unwrap();
+    let action = Actions::UpdateLeverage(UpdateLeverage {
+        asset: 0,
+        is_cross: true,
+        leverage: 3,
+    });
+    let nonce = chrono::Utc::now().timestamp_millis() as u64;
+
+    let mut transaction = exchange_client
+        .multi_sig_transaction(multi_sig_user, action, nonce)
+        .await
+        .unwrap();
+    transaction.sign(&outer_wallet).unwrap();
+
+    // The partially signed transaction can be sent to the other signers as JSON
+    let json = transaction.to_json().unwrap();
+    info!("Partially signed transaction: {json}");
+    let mut remote = MultiSigTransaction::from_json(&json).unwrap();
+    remote.sign(&other_wallet).unwrap();
+    transaction.merge(remote).unwrap();
+
+    let response = exchange_client.multi_sig(&transaction).await.unwrap();
+    info!("Multi-sig response: {response:?}");
+}
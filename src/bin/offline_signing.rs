@@ -0,0 +1,32 @@
+use hyperliquid_rust_sdk::{
+    Actions, BaseUrl, ExchangeClient, LocalWallet, SigningEnvelope, UpdateLeverage,
+};
+use log::info;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+
+    // On the online machine: export the unsigned action
+    let action = Actions::UpdateLeverage(UpdateLeverage {
+        asset: 0,
+        is_cross: true,
+        leverage: 3,
+    });
+    let json = exchange_client.signing_envelope(action).unwrap().to_json().unwrap();
+    info!("Unsigned envelope: {json}");
+
+    // On the air-gapped machine: sign it without any network access
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+    let wallet = priv_key.parse::<LocalWallet>().unwrap();
+    let mut envelope = SigningEnvelope::from_json(&json).unwrap();
+    envelope.sign(&wallet).unwrap();
+    let signed_json = envelope.to_json().unwrap();
+
+    // Back online: broadcast the signed envelope
+    let envelope = SigningEnvelope::from_json(&signed_json).unwrap();
+    let response = exchange_client.broadcast(&envelope).await.unwrap();
+    info!("Broadcast response: {response:?}");
+}
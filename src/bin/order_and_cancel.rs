@@ -2,8 +2,8 @@ use hyperliquid_rust_sdk::LocalWallet;
 use log::info;
 
 use hyperliquid_rust_sdk::{
-    BaseUrl, ClientCancelRequest, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
-    ExchangeDataStatus, ExchangeResponseStatus,
+    BaseUrl, ClientCancelRequest, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus,
+    Order,
 };
 use std::{thread::sleep, time::Duration};
 
@@ -15,19 +15,11 @@ async fn main() {
         .parse()
         .unwrap();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
-    let order = ClientOrderRequest {
-        asset: "ETH".to_string(),
-        is_buy: true,
-        reduce_only: false,
-        limit_px: 1800.0,
-        sz: 0.01,
-        cloid: None,
-        order_type: ClientOrder::Limit(ClientLimit {
-            tif: "Gtc".to_string(),
-        }),
-    };
+    let order = Order::buy("ETH").size(0.01).limit(1800).build().unwrap();
 
     let response = exchange_client.order(order, None).await.unwrap();
     info!("Order placed: {response:?}");
@@ -38,8 +30,8 @@ async fn main() {
     };
     let status = response.data.unwrap().statuses[0].clone();
     let oid = match status {
-        ExchangeDataStatus::Filled(order) => order.order.oid,
-        ExchangeDataStatus::Resting(order) => order.order.oid,
+        ExchangeDataStatus::Filled(order) => order.oid,
+        ExchangeDataStatus::Resting(order) => order.oid,
         _ => panic!("Unexpected order status"),
     };
 
@@ -52,6 +44,6 @@ async fn main() {
     };
 
     // This response will return an error if order was filled (since you can't cancel a filled order), otherwise it will cancel the order
-    let response = exchange_client.cancel(cancel, None).await.unwrap();
+    let response = exchange_client.cancel(cancel).await.unwrap();
     info!("Order potentially cancelled: {response:?}");
 }
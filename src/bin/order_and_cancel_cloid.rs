@@ -4,8 +4,8 @@ use log::info;
 use uuid::Uuid;
 
 use hyperliquid_rust_sdk::{
-    BaseUrl, ClientCancelRequestCloid, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
-    ExchangeDataStatus, ExchangeResponseStatus,
+    BaseUrl, ClientCancelRequestCloid, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus,
+    Order,
 };
 use std::{thread::sleep, time::Duration};
 
@@ -16,20 +16,17 @@ async fn main() {
     let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
     let wallet = priv_key.parse::<PrivateKeySigner>().unwrap();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
     let cloid = Uuid::new_v4();
-    let order = ClientOrderRequest {
-        asset: "ETH".to_string(),
-        is_buy: true,
-        reduce_only: false,
-        limit_px: 2000.0,
-        sz: 0.1,
-        cloid: Some(cloid),
-        order_type: ClientOrder::Limit(ClientLimit {
-            tif: "Gtc".to_string(),
-        }),
-    };
+    let order = Order::buy("ETH")
+        .size(0.1)
+        .limit(2000)
+        .cloid(cloid)
+        .build()
+        .unwrap();
 
     let response = exchange_client.order(order, None).await.unwrap();
     info!("Order placed: {response:?}");
@@ -54,6 +51,6 @@ async fn main() {
     };
 
     // This response will return an error if order was filled (since you can't cancel a filled order), otherwise it will cancel the order
-    let response = exchange_client.cancel_by_cloid(cancel, None).await.unwrap();
+    let response = exchange_client.cancel_by_cloid(cancel).await.unwrap();
     info!("Order potentially cancelled: {response:?}");
 }
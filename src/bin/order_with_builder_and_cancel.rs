@@ -15,7 +15,9 @@ async fn main() {
     let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
     let wallet = priv_key.parse::<LocalWallet>().unwrap();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
     let order = ClientOrderRequest {
         asset: "ETH".to_string(),
@@ -35,7 +37,7 @@ async fn main() {
     };
 
     info!("Placing order with builder: {:?}", order);
-    let res = exchange_client.order(order.clone(), Some(builder.clone())).await.unwrap();
+    let res = exchange_client.order(order.clone(), Some(builder)).await.unwrap();
     info!("Order result: {:?}", res);
 
     let response = match res {
@@ -46,8 +48,8 @@ async fn main() {
     let oid = if let Some(data) = response.data {
         if !data.statuses.is_empty() {
             match data.statuses[0].clone() {
-                ExchangeDataStatus::Filled(order) => order.order.oid,
-                ExchangeDataStatus::Resting(order) => order.order.oid,
+                ExchangeDataStatus::Filled(order) => order.oid,
+                ExchangeDataStatus::Resting(order) => order.oid,
                 ExchangeDataStatus::Error(e) => panic!("error with order: {e}"),
                 _ => unreachable!(),
             }
@@ -67,6 +69,6 @@ async fn main() {
     };
 
     info!("Cancelling order");
-    let res = exchange_client.cancel(cancel, Some(builder)).await.unwrap();
+    let res = exchange_client.cancel(cancel).await.unwrap();
     info!("Cancel result: {:?}", res);
 }
@@ -1,6 +1,5 @@
-use alloy_primitives::U256;
 use alloy_signer_local::PrivateKeySigner;
-use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient};
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, InfoClient};
 use log::info;
 
 #[tokio::main]
@@ -9,14 +8,21 @@ async fn main() {
     // Key was randomly generated for testing and shouldn't be used with any real funds
     let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
     let wallet = priv_key.parse::<PrivateKeySigner>().unwrap();
+    let user = wallet.address();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
+    let info_client = InfoClient::new(BaseUrl::Testnet.get_url());
 
     let code = "TESTNET".to_string();
 
     let res = exchange_client.set_referrer(code).await;
     match res {
-        Ok(_) => info!("Successfully set referrer code"),
+        Ok(response) => info!("Set referrer code: {response:?}"),
         Err(e) => eprintln!("Failed to set referrer code: {}", e),
     }
+
+    let state = info_client.query_referral_state(user).await.unwrap();
+    info!("Referrer stage: {:?}", state.referrer_state);
 }
@@ -4,7 +4,7 @@ use log::info;
 
 use hyperliquid_rust_sdk::{
     BaseUrl, ClientCancelRequest, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
-    ExchangeDataStatus, ExchangeResponseStatus,
+    ExchangeDataStatus, ExchangeResponseStatus, InfoClient,
 };
 use std::{thread::sleep, time::Duration};
 
@@ -16,7 +16,9 @@ async fn main() {
     let wallet = priv_key.parse::<PrivateKeySigner>().unwrap();
     let user = wallet.address();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
     let order = ClientOrderRequest {
         asset: "XYZTWO/USDC".to_string(),
@@ -39,8 +41,8 @@ async fn main() {
     };
     let status = response.data.unwrap().statuses[0].clone();
     let oid = match status {
-        ExchangeDataStatus::Filled(order_info) => order_info.order.oid,
-        ExchangeDataStatus::Resting(order_info) => order_info.order.oid,
+        ExchangeDataStatus::Filled(order_info) => order_info.oid,
+        ExchangeDataStatus::Resting(order_info) => order_info.oid,
         _ => panic!("Unexpected order status"),
     };
 
@@ -53,6 +55,13 @@ async fn main() {
     };
 
     // This response will return an error if order was filled (since you can't cancel a filled order), otherwise it will cancel the order
-    let response = exchange_client.cancel(cancel, None).await.unwrap();
+    let response = exchange_client.cancel(cancel).await.unwrap();
     info!("Order potentially cancelled: {response:?}");
+
+    let info_client = InfoClient::new(BaseUrl::Testnet.get_url());
+    let value = info_client.spot_balances_usdc_value(user).await.unwrap();
+    info!(
+        "Spot balances of {user} are worth {} USDC, not counting {:?}",
+        value.total, value.unpriced
+    );
 }
@@ -1,7 +1,7 @@
+use alloy_primitives::Address;
 use hyperliquid_rust_sdk::LocalWallet;
 use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient};
 use log::info;
-use alloy_primitives::{Address, U256};
 
 #[tokio::main]
 async fn main() {
@@ -11,17 +11,18 @@ async fn main() {
         .parse()
         .unwrap();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
 
     let amount = "1"; // 1 USD
     let destination = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414";
 
-    let amount = amount.parse::<U256>().unwrap();
     let destination = destination.parse::<Address>().unwrap();
 
-    exchange_client
-        .usd_send(destination, amount, "Testnet".to_string())
+    let status = exchange_client
+        .usd_send(destination, amount)
         .await
         .unwrap();
-    info!("USD transfer completed");
+    info!("USD transfer status: {status:?}");
 }
@@ -1,6 +1,5 @@
-use alloy_primitives::{Address, U256};
-use alloy_signer_local::PrivateKeySigner;
-use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient};
+use alloy_primitives::Address;
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, InfoClient, LocalWallet};
 use log::info;
 
 #[tokio::main]
@@ -8,18 +7,28 @@ async fn main() {
     env_logger::init();
     // Key was randomly generated for testing and shouldn't be used with any real funds
     let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
-    let wallet = priv_key.parse::<PrivateKeySigner>().unwrap();
+    let wallet = priv_key.parse::<LocalWallet>().unwrap();
+    let user = wallet.address();
 
-    let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+    let exchange_client = ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), wallet, None)
+        .await
+        .unwrap();
+    let info_client = InfoClient::new(BaseUrl::Testnet.get_url());
 
     let vault_address = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".parse::<Address>().unwrap();
-    let amount = "1"; // 1 USD
+    let usd = 1_000_000; // 1 USD
 
-    info!("Depositing {} USD to vault {}", amount, vault_address);
+    info!("Depositing {usd} micro USD to vault {vault_address}");
 
     let response = exchange_client
-        .vault_transfer(vault_address, true, amount.to_string(), "Testnet".to_string())
+        .vault_transfer(vault_address, true, usd)
         .await
         .unwrap();
     info!("Vault deposit response: {response:?}");
+
+    let details = info_client
+        .vault_details(vault_address, Some(user))
+        .await
+        .unwrap();
+    info!("Vault follower state: {:?}", details.follower_state);
 }
@@ -1,6 +1,6 @@
 use log::info;
 
-use hyperliquid_rust_sdk::{info::InfoClient, helpers::BaseUrl, Message, Subscription};
+use hyperliquid_rust_sdk::{info::InfoClient, helpers::BaseUrl, L2BookOptions, Message, Subscription};
 use tokio::{
     spawn,
     sync::mpsc::unbounded_channel,
@@ -18,6 +18,7 @@ async fn main() {
         .subscribe(
             Subscription::L2Book {
                 coin: "ETH".to_string(),
+                options: L2BookOptions::default(),
             },
             sender,
         )
@@ -0,0 +1,419 @@
+//! Deposits of Arbitrum USDC into Hyperliquid through the bridge contract.
+//!
+//! Deposits are authorized with EIP-2612 permits instead of an `approve` transaction, so the
+//! depositor only signs and anyone, e.g. a relayer, can submit the deposit for them.
+
+use alloy_dyn_abi::Eip712Domain;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_sol_types::{SolCall, SolStruct};
+use reqwest::Client;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    constants::Chain,
+    errors::{HyperliquidError, Result},
+    signature::{create_signature::SignatureRsv, HyperliquidSigner},
+};
+
+/// The bridge does not credit deposits below 5 USDC; they are lost.
+pub const MIN_DEPOSIT_USD: u64 = 5_000_000;
+
+mod abi {
+    use alloy_sol_types::sol;
+
+    sol! {
+        struct Permit {
+            address owner;
+            address spender;
+            uint256 value;
+            uint256 nonce;
+            uint256 deadline;
+        }
+
+        struct Signature {
+            uint256 r;
+            uint256 s;
+            uint8 v;
+        }
+
+        struct DepositWithPermit {
+            address user;
+            uint64 usd;
+            uint64 deadline;
+            Signature signature;
+        }
+
+        function batchedDepositWithPermit(DepositWithPermit[] deposits);
+
+        function nonces(address owner) returns (uint256 nonce);
+    }
+}
+
+/// EIP-712 domain of the USDC contract's permits; testnet USDC names itself differently.
+fn usdc_permit_domain(chain: Chain) -> Eip712Domain {
+    let (name, version) = match chain {
+        Chain::Mainnet => ("USD Coin", "2"),
+        Chain::Testnet => ("USDC2", "1"),
+    };
+    Eip712Domain::new(
+        Some(name.into()),
+        Some(version.into()),
+        Some(U256::from(chain.chain_id())),
+        Some(chain.usdc_address()),
+        None,
+    )
+}
+
+/// An EIP-2612 permit allowing the bridge to pull `usd` (in millionths of a USDC) from
+/// `owner` until `deadline` (in seconds since the epoch).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsdcPermit {
+    pub owner: Address,
+    pub usd: u64,
+    /// The owner's current permit nonce on the USDC contract.
+    pub nonce: U256,
+    pub deadline: u64,
+}
+
+impl UsdcPermit {
+    /// The EIP-712 digest the owner signs.
+    pub fn signing_hash(&self, chain: Chain) -> B256 {
+        abi::Permit {
+            owner: self.owner,
+            spender: chain.bridge_address(),
+            value: U256::from(self.usd),
+            nonce: self.nonce,
+            deadline: U256::from(self.deadline),
+        }
+        .eip712_signing_hash(&usdc_permit_domain(chain))
+    }
+
+    /// Signs the permit as its owner, producing a deposit ready to be submitted.
+    pub fn sign(&self, chain: Chain, signer: &impl HyperliquidSigner) -> Result<DepositPermit> {
+        if self.usd < MIN_DEPOSIT_USD {
+            return Err(HyperliquidError::InvalidParameter(format!(
+                "deposit of {} is below the minimum of {MIN_DEPOSIT_USD}",
+                self.usd
+            )));
+        }
+        if signer.address() != self.owner {
+            return Err(HyperliquidError::SignatureError(format!(
+                "permit of {} cannot be signed by {}",
+                self.owner,
+                signer.address()
+            )));
+        }
+        let signature = signer.sign_hash(self.signing_hash(chain))?.to_rsv();
+        Ok(DepositPermit {
+            user: self.owner,
+            usd: self.usd,
+            deadline: self.deadline,
+            signature,
+        })
+    }
+}
+
+/// A signed deposit, one entry of `batchedDepositWithPermit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepositPermit {
+    pub user: Address,
+    pub usd: u64,
+    pub deadline: u64,
+    pub signature: SignatureRsv,
+}
+
+/// Calldata of the bridge's `batchedDepositWithPermit` crediting all `deposits` at once.
+pub fn batched_deposit_with_permit_calldata(deposits: &[DepositPermit]) -> Bytes {
+    let deposits = deposits
+        .iter()
+        .map(|deposit| abi::DepositWithPermit {
+            user: deposit.user,
+            usd: deposit.usd,
+            deadline: deposit.deadline,
+            signature: abi::Signature {
+                r: deposit.signature.r,
+                s: deposit.signature.s,
+                v: deposit.signature.v as u8,
+            },
+        })
+        .collect();
+    abi::batchedDepositWithPermitCall { deposits }
+        .abi_encode()
+        .into()
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// Submits bridge deposits through an Arbitrum JSON-RPC endpoint.
+///
+/// Transactions are sent with `eth_sendTransaction`, so the node has to sign for the sender,
+/// as a local anvil fork does for its dev accounts or with `--auto-impersonate`.
+#[derive(Debug)]
+pub struct BridgeClient {
+    http_client: Client,
+    rpc_url: String,
+    chain: Chain,
+}
+
+impl BridgeClient {
+    pub fn new(rpc_url: String, chain: Chain) -> Self {
+        Self {
+            http_client: Client::new(),
+            rpc_url,
+            chain,
+        }
+    }
+
+    async fn rpc<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let text = self
+            .http_client
+            .post(&self.rpc_url)
+            .header("Content-Type", "application/json")
+            .body(request.to_string())
+            .send()
+            .await?
+            .text()
+            .await?;
+        let response: RpcResponse<T> = serde_json::from_str(&text)?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(HyperliquidError::InvalidResponse(format!(
+                "{method} failed with {}: {}",
+                error.code, error.message
+            ))),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(HyperliquidError::InvalidResponse(format!(
+                "{method} returned no result"
+            ))),
+        }
+    }
+
+    /// The current permit nonce of `owner` on the USDC contract.
+    pub async fn permit_nonce(&self, owner: Address) -> Result<U256> {
+        let data: Bytes = abi::noncesCall { owner }.abi_encode().into();
+        let result: Bytes = self
+            .rpc(
+                "eth_call",
+                json!([{"to": self.chain.usdc_address(), "data": data}, "latest"]),
+            )
+            .await?;
+        let nonce = abi::noncesCall::abi_decode_returns(&result, true)
+            .map_err(|e| HyperliquidError::InvalidResponse(e.to_string()))?
+            .nonce;
+        Ok(nonce)
+    }
+
+    /// Signs a deposit of `usd` (in millionths of a USDC) valid until `deadline` (in seconds
+    /// since the epoch), using the signer's current permit nonce.
+    pub async fn sign_deposit(
+        &self,
+        signer: &impl HyperliquidSigner,
+        usd: u64,
+        deadline: u64,
+    ) -> Result<DepositPermit> {
+        let permit = UsdcPermit {
+            owner: signer.address(),
+            usd,
+            nonce: self.permit_nonce(signer.address()).await?,
+            deadline,
+        };
+        permit.sign(self.chain, signer)
+    }
+
+    /// Sends a `batchedDepositWithPermit` transaction from `from`, returning its hash.
+    pub async fn submit_deposits(&self, from: Address, deposits: &[DepositPermit]) -> Result<B256> {
+        if deposits.is_empty() {
+            return Err(HyperliquidError::InvalidParameter(
+                "no deposits to submit".to_string(),
+            ));
+        }
+        let transaction = json!({
+            "from": from,
+            "to": self.chain.bridge_address(),
+            "data": batched_deposit_with_permit_calldata(deposits),
+        });
+        self.rpc("eth_sendTransaction", json!([transaction])).await
+    }
+
+    /// Signs a deposit and submits it from the signer's own account.
+    pub async fn deposit(
+        &self,
+        signer: &impl HyperliquidSigner,
+        usd: u64,
+        deadline: u64,
+    ) -> Result<B256> {
+        let deposit = self.sign_deposit(signer, usd, deadline).await?;
+        self.submit_deposits(signer.address(), &[deposit]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{signature::recover_signer, LocalWallet};
+    use alloy_primitives::keccak256;
+    use mockito::Matcher;
+
+    fn wallet() -> LocalWallet {
+        "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_permit_signing_hash() {
+        let permit = UsdcPermit {
+            owner: wallet().address(),
+            usd: 10_000_000,
+            nonce: U256::from(3),
+            deadline: 1700000000,
+        };
+        let typed_data: alloy_dyn_abi::TypedData = serde_json::from_value(json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Permit": [
+                    {"name": "owner", "type": "address"},
+                    {"name": "spender", "type": "address"},
+                    {"name": "value", "type": "uint256"},
+                    {"name": "nonce", "type": "uint256"},
+                    {"name": "deadline", "type": "uint256"}
+                ]
+            },
+            "primaryType": "Permit",
+            "domain": {
+                "name": "USD Coin",
+                "version": "2",
+                "chainId": 42161,
+                "verifyingContract": "0xaf88d065e77c8cC2239327C5EDb3A432268e5831"
+            },
+            "message": {
+                "owner": wallet().address(),
+                "spender": "0x2Df1c51E09aECF9cacB7bc98cB1742757f163dF7",
+                "value": 10000000,
+                "nonce": 3,
+                "deadline": 1700000000
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            permit.signing_hash(Chain::Mainnet),
+            typed_data.eip712_signing_hash().unwrap()
+        );
+        assert_ne!(
+            permit.signing_hash(Chain::Mainnet),
+            permit.signing_hash(Chain::Testnet)
+        );
+
+        let deposit = permit.sign(Chain::Mainnet, &wallet()).unwrap();
+        assert_eq!(
+            recover_signer(permit.signing_hash(Chain::Mainnet), &deposit.signature).unwrap(),
+            wallet().address()
+        );
+
+        let too_small = UsdcPermit {
+            usd: MIN_DEPOSIT_USD - 1,
+            ..permit
+        };
+        assert!(too_small.sign(Chain::Mainnet, &wallet()).is_err());
+    }
+
+    #[test]
+    fn test_batched_deposit_calldata() {
+        let deposit = DepositPermit {
+            user: wallet().address(),
+            usd: 10_000_000,
+            deadline: 1700000000,
+            signature: SignatureRsv {
+                r: U256::from(1),
+                s: U256::from(2),
+                v: 27,
+            },
+        };
+        let calldata = batched_deposit_with_permit_calldata(&[deposit]);
+        let selector = keccak256(
+            "batchedDepositWithPermit((address,uint64,uint64,(uint256,uint256,uint8))[])",
+        );
+        assert_eq!(calldata[..4], selector[..4]);
+
+        let decoded = abi::batchedDepositWithPermitCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.deposits.len(), 1);
+        assert_eq!(decoded.deposits[0].user, deposit.user);
+        assert_eq!(decoded.deposits[0].usd, deposit.usd);
+        assert_eq!(decoded.deposits[0].signature.v, 27);
+    }
+
+    #[tokio::test]
+    async fn test_deposit() {
+        let mut server = mockito::Server::new_async().await;
+        let client = BridgeClient::new(server.url(), Chain::Testnet);
+        let nonce_mock = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({"method": "eth_call"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:064x}"}}"#,
+                7
+            ))
+            .create_async()
+            .await;
+        let send_mock = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({
+                "method": "eth_sendTransaction",
+                "params": [{"to": Chain::Testnet.bridge_address()}]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"jsonrpc":"2.0","id":1,"result":"{}"}}"#,
+                B256::repeat_byte(0xab)
+            ))
+            .create_async()
+            .await;
+
+        let deposit = client
+            .sign_deposit(&wallet(), 10_000_000, 1700000000)
+            .await
+            .unwrap();
+        let permit = UsdcPermit {
+            owner: wallet().address(),
+            usd: 10_000_000,
+            nonce: U256::from(7),
+            deadline: 1700000000,
+        };
+        assert_eq!(
+            recover_signer(permit.signing_hash(Chain::Testnet), &deposit.signature).unwrap(),
+            wallet().address()
+        );
+
+        let hash = client
+            .submit_deposits(wallet().address(), &[deposit])
+            .await
+            .unwrap();
+        assert_eq!(hash, B256::repeat_byte(0xab));
+        nonce_mock.assert_async().await;
+        send_mock.assert_async().await;
+    }
+}
@@ -1,3 +1,6 @@
+use alloy_primitives::{address, Address};
+use serde::{Deserialize, Serialize};
+
 pub const MAINNET_API_URL: &str = "https://api.hyperliquid.xyz";
 pub const TESTNET_API_URL: &str = "https://api.testnet.hyperliquid.xyz";
 pub const LOCAL_API_URL: &str = "http://localhost:3001";
@@ -5,11 +8,28 @@ pub const LOCAL_API_URL: &str = "http://localhost:3001";
 pub(crate) const ARBITRUM_CHAIN_ID: u64 = 42161;
 pub(crate) const ARBITRUM_TESTNET_CHAIN_ID: u64 = 421614;
 
+// Hyperliquid bridge contracts and the USDC they accept on Arbitrum
+pub(crate) const MAINNET_BRIDGE_ADDRESS: Address =
+    address!("2df1c51e09aecf9cacb7bc98cb1742757f163df7");
+pub(crate) const TESTNET_BRIDGE_ADDRESS: Address =
+    address!("08cfc1b6b2dcf36a1480b99353a354aa8ac56f89");
+pub(crate) const MAINNET_USDC_ADDRESS: Address =
+    address!("af88d065e77c8cc2239327c5edb3a432268e5831");
+pub(crate) const TESTNET_USDC_ADDRESS: Address =
+    address!("1baabb04529d43a73232b713c0fe471f7c7334d5");
+
+// Spot tokens sent to a token's system address move to HyperEVM: `0x20` followed by the
+// big-endian token index, except HYPE, the native gas token
+pub(crate) const SYSTEM_ADDRESS_PREFIX: u8 = 0x20;
+pub(crate) const HYPE_TOKEN_NAME: &str = "HYPE";
+pub(crate) const HYPE_SYSTEM_ADDRESS: Address =
+    address!("2222222222222222222222222222222222222222");
+
 pub const EPSILON: f64 = 1e-9;
 pub(crate) const INF_BPS: u16 = 10_001;
 
 // Chain identifiers
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Chain {
     Mainnet,
     Testnet,
@@ -23,6 +43,34 @@ impl Chain {
         }
     }
 
+    /// The `hyperliquidChain` actions are signed for.
+    pub fn hyperliquid_chain(&self) -> &'static str {
+        match self {
+            Chain::Mainnet => "Mainnet",
+            Chain::Testnet => "Testnet",
+        }
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        *self == Chain::Mainnet
+    }
+
+    /// The Hyperliquid bridge contract on Arbitrum.
+    pub fn bridge_address(&self) -> Address {
+        match self {
+            Chain::Mainnet => MAINNET_BRIDGE_ADDRESS,
+            Chain::Testnet => TESTNET_BRIDGE_ADDRESS,
+        }
+    }
+
+    /// The USDC contract on Arbitrum the bridge accepts deposits of.
+    pub fn usdc_address(&self) -> Address {
+        match self {
+            Chain::Mainnet => MAINNET_USDC_ADDRESS,
+            Chain::Testnet => TESTNET_USDC_ADDRESS,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Chain::Mainnet => "arbitrum",
@@ -58,4 +106,16 @@ pub(crate) const MAX_ORDERS_PER_REQUEST: usize = 100;
 // Decimal precision
 pub(crate) const USD_DECIMALS: u32 = 6;
 pub(crate) const PRICE_DECIMALS: u32 = 8;
-pub(crate) const SIZE_DECIMALS: u32 = 8; 
\ No newline at end of file
+pub(crate) const SIZE_DECIMALS: u32 = 8; 
+// Spot assets are addressed as 10000 + their index in the spot universe
+pub(crate) const SPOT_ASSET_OFFSET: u32 = 10_000;
+
+// Prices may have at most this many decimals, minus the asset's size decimals
+pub(crate) const MAX_PERP_PX_DECIMALS: u32 = 6;
+pub(crate) const MAX_SPOT_PX_DECIMALS: u32 = 8;
+pub(crate) const MAX_PX_SIG_FIGS: i32 = 5;
+
+// Assets of the n-th builder-deployed perp DEX (n >= 1) are addressed from
+// 110000 + (n - 1) * 10000
+pub(crate) const PERP_DEX_OFFSET_BASE: u32 = 110_000;
+pub(crate) const PERP_DEX_OFFSET_STRIDE: u32 = 10_000;
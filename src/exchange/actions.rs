@@ -1,29 +1,35 @@
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_sol_types::sol;
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    prelude::*,
+    signature::{create_signature::SignatureRsv, l1_action_signing_hash, user_signed_action_hash},
+    Error,
+};
+
 use super::{
     cancel::{CancelRequest, CancelRequestCloid}, 
     modify::ModifyRequest, 
     order::OrderRequest,
+    perp_deploy::PerpDeploy,
+    spot_deploy::SpotDeploy,
+    exchange_payload::OrderedJson,
     BuilderInfo
 };
 
 pub(crate) const HYPERLIQUID_EIP_PREFIX: &str = "HyperliquidTransaction:";
 
+pub(crate) const SEND_MULTI_SIG_FIELDS: &[(&str, &str)] = &[
+    ("hyperliquidChain", "string"),
+    ("multiSigActionHash", "bytes32"),
+    ("nonce", "uint64"),
+];
+
 pub(crate) mod types {
     use super::*;
     
     sol! {
-        #[derive(Debug, Serialize, Deserialize)]
-        struct UsdSend {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            address destination;
-            uint256 amount;
-            uint256 time;
-        }
-
         #[derive(Debug, Serialize, Deserialize)]
         struct ApproveAgent {
             uint256 signatureChainId;
@@ -32,25 +38,6 @@ pub(crate) mod types {
             uint256 time;
         }
 
-        #[derive(Debug, Serialize, Deserialize)]
-        struct Withdraw3 {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            address destination;
-            uint256 amount;
-            uint256 time;
-        }
-
-        #[derive(Debug, Serialize, Deserialize)]
-        struct SpotSend {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            address destination;
-            string token;
-            uint256 amount;
-            uint256 time;
-        }
-
         #[derive(Debug, Serialize, Deserialize)]
         struct ClassTransfer {
             uint256 signatureChainId;
@@ -59,36 +46,6 @@ pub(crate) mod types {
             bool toPerp;
             uint256 time;
         }
-
-        #[derive(Debug, Serialize, Deserialize)]
-        struct Stake {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            uint256 amount;
-            uint256 time;
-        }
-
-        #[derive(Debug, Serialize, Deserialize)]
-        struct StartUnstake {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            uint256 amount;
-            uint256 time;
-        }
-
-        #[derive(Debug, Serialize, Deserialize)]
-        struct CompleteUnstake {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            uint256 time;
-        }
-
-        #[derive(Debug, Serialize, Deserialize)]
-        struct ClaimRewards {
-            uint256 signatureChainId;
-            string hyperliquidChain;
-            uint256 time;
-        }
     }
 }
 
@@ -104,7 +61,7 @@ pub struct UpdateLeverage {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct UpdateIsolatedMargin {
+pub struct UpdateIsolatedMargin {
     pub asset: u32,
     pub is_buy: bool,
     pub ntli: i64,
@@ -139,9 +96,37 @@ pub struct BulkCancelCloid {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct VaultTransfer {
-    pub vault_address: Address,
+    pub vault_address: String,
     pub is_deposit: bool,
-    pub usd: String,
+    /// Amount in millionths of a USDC.
+    pub usd: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVault {
+    pub name: String,
+    pub description: String,
+    /// Leader's initial deposit in millionths of a USDC.
+    pub initial_usd: u64,
+    pub nonce: u64,
+}
+
+/// Changes the settings of a vault; `None` leaves a setting unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultModify {
+    pub vault_address: String,
+    pub allow_deposits: Option<bool>,
+    pub always_close_on_withdraw: Option<bool>,
+}
+
+/// Distributes `usd` (in millionths of a USDC) of a vault's equity to its followers.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDistribute {
+    pub vault_address: String,
+    pub usd: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -150,29 +135,806 @@ pub struct SetReferrer {
     pub code: String,
 }
 
+/// Creates the referral code `code` for the user.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterReferrer {
+    pub code: String,
+}
+
+/// Switches the user's HyperEVM transactions between small, fast blocks and large, slow
+/// blocks with a higher gas limit, e.g. for contract deployments.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmUserModify {
+    pub using_big_blocks: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ApproveBuilderFee {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
     pub max_fee_rate: String,
     pub builder: String,
     pub nonce: u64,
+}
+
+/// Actions signed by the user's key over EIP-712 typed data rather than through an L1 agent.
+pub(crate) trait UserSignedAction: Serialize + Into<Actions> {
+    const PRIMARY_TYPE: &'static str;
+    const FIELDS: &'static [(&'static str, &'static str)];
+
+    fn signature_chain_id(&self) -> U256;
+
+    fn signing_hash(&self) -> Result<B256> {
+        let message = serde_json::to_value(self)?;
+        user_signed_action_hash(
+            &format!("{HYPERLIQUID_EIP_PREFIX}{}", Self::PRIMARY_TYPE),
+            Self::FIELDS,
+            &message,
+            self.signature_chain_id(),
+        )
+    }
+}
+
+impl UserSignedAction for ApproveBuilderFee {
+    const PRIMARY_TYPE: &'static str = "ApproveBuilderFee";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("maxFeeRate", "string"),
+        ("builder", "address"),
+        ("nonce", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<ApproveBuilderFee> for Actions {
+    fn from(action: ApproveBuilderFee) -> Self {
+        Actions::ApproveBuilderFee(action)
+    }
+}
+
+/// Sends `amount` USDC, a decimal string such as `"12.5"`, from the perp balance to
+/// `destination`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UsdSend {
     pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub destination: String,
+    pub amount: String,
+    pub time: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl UserSignedAction for UsdSend {
+    const PRIMARY_TYPE: &'static str = "UsdSend";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("destination", "string"),
+        ("amount", "string"),
+        ("time", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<UsdSend> for Actions {
+    fn from(action: UsdSend) -> Self {
+        Actions::UsdSend(action)
+    }
+}
+
+/// Withdraws `amount` USDC from the perp balance to `destination` on Arbitrum through the
+/// bridge.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdraw3 {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub destination: String,
+    pub amount: String,
+    pub time: u64,
+}
+
+impl UserSignedAction for Withdraw3 {
+    const PRIMARY_TYPE: &'static str = "Withdraw";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("destination", "string"),
+        ("amount", "string"),
+        ("time", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<Withdraw3> for Actions {
+    fn from(action: Withdraw3) -> Self {
+        Actions::Withdraw3(action)
+    }
+}
+
+/// Sends `amount` of the spot `token`, given as `NAME:0xtokenId`, to `destination`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotSend {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub destination: String,
+    pub token: String,
+    pub amount: String,
+    pub time: u64,
+}
+
+impl UserSignedAction for SpotSend {
+    const PRIMARY_TYPE: &'static str = "SpotSend";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("destination", "string"),
+        ("token", "string"),
+        ("amount", "string"),
+        ("time", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<SpotSend> for Actions {
+    fn from(action: SpotSend) -> Self {
+        Actions::SpotSend(action)
+    }
+}
+
+/// Moves `wei` of HYPE from the spot balance into the staking balance.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CDeposit {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for CDeposit {
+    const PRIMARY_TYPE: &'static str = "CDeposit";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("wei", "uint64"),
+        ("nonce", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<CDeposit> for Actions {
+    fn from(action: CDeposit) -> Self {
+        Actions::CDeposit(action)
+    }
+}
+
+/// Moves `wei` of undelegated HYPE from the staking balance back to the spot balance, which
+/// completes after the unstaking queue.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CWithdraw {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub wei: u64,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for CWithdraw {
+    const PRIMARY_TYPE: &'static str = "CWithdraw";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("wei", "uint64"),
+        ("nonce", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<CWithdraw> for Actions {
+    fn from(action: CWithdraw) -> Self {
+        Actions::CWithdraw(action)
+    }
+}
+
+/// Delegates `wei` of staked HYPE to `validator`, or undelegates it when `is_undelegate`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDelegate {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub validator: String,
+    pub wei: u64,
+    pub is_undelegate: bool,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for TokenDelegate {
+    const PRIMARY_TYPE: &'static str = "TokenDelegate";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("validator", "address"),
+        ("wei", "uint64"),
+        ("isUndelegate", "bool"),
+        ("nonce", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<TokenDelegate> for Actions {
+    fn from(action: TokenDelegate) -> Self {
+        Actions::TokenDelegate(action)
+    }
+}
+
+/// Sets the authorized users and signature threshold of a multi-sig user as a JSON encoded
+/// [`MultiSigSigners`](super::MultiSigSigners), or converts it back to a normal user with
+/// `"null"`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertToMultiSigUser {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    pub signers: String,
+    pub nonce: u64,
+}
+
+impl UserSignedAction for ConvertToMultiSigUser {
+    const PRIMARY_TYPE: &'static str = "ConvertToMultiSigUser";
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("hyperliquidChain", "string"),
+        ("signers", "string"),
+        ("nonce", "uint64"),
+    ];
+
+    fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+}
+
+impl From<ConvertToMultiSigUser> for Actions {
+    fn from(action: ConvertToMultiSigUser) -> Self {
+        Actions::ConvertToMultiSigUser(action)
+    }
+}
+
+/// An action of `multi_sig_user` signed by its authorized users and submitted by
+/// `outer_signer`, one of them.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSig {
+    pub signature_chain_id: U256,
+    pub signatures: Vec<SignatureRsv>,
+    pub payload: MultiSigPayload,
+}
+
+impl MultiSig {
+    /// The outer signer signs the hash of the action, without its type, as a user-signed
+    /// `SendMultiSig` action.
+    fn signing_hash(
+        &self,
+        nonce: u64,
+        vault_address: Option<Address>,
+        is_mainnet: bool,
+    ) -> Result<B256> {
+        let multi_sig_action_hash = action_hash(self, nonce, vault_address)?;
+        let message = serde_json::json!({
+            "hyperliquidChain": if is_mainnet { "Mainnet" } else { "Testnet" },
+            "multiSigActionHash": multi_sig_action_hash.to_string(),
+            "nonce": nonce,
+        });
+        user_signed_action_hash(
+            &format!("{HYPERLIQUID_EIP_PREFIX}SendMultiSig"),
+            SEND_MULTI_SIG_FIELDS,
+            &message,
+            self.signature_chain_id,
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigPayload {
+    pub multi_sig_user: String,
+    pub outer_signer: String,
+    pub action: Box<Actions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
+#[serde(rename_all = "camelCase")]
 pub enum Actions {
     UpdateLeverage(UpdateLeverage),
     UpdateIsolatedMargin(UpdateIsolatedMargin),
+    #[serde(rename = "order")]
     BulkOrder(BulkOrder),
+    #[serde(rename = "cancel")]
     BulkCancel(BulkCancel),
+    #[serde(rename = "batchModify")]
     BulkModify(BulkModify),
+    #[serde(rename = "cancelByCloid")]
     BulkCancelCloid(BulkCancelCloid),
     VaultTransfer(VaultTransfer),
+    CreateVault(CreateVault),
+    VaultModify(VaultModify),
+    VaultDistribute(VaultDistribute),
     SetReferrer(SetReferrer),
+    RegisterReferrer(RegisterReferrer),
+    /// Claims the user's unclaimed referral rewards.
+    ClaimRewards,
+    EvmUserModify(EvmUserModify),
+    UsdSend(UsdSend),
+    Withdraw3(Withdraw3),
+    SpotSend(SpotSend),
     ApproveBuilderFee(ApproveBuilderFee),
-    Stake(Stake),
-    StartUnstake(StartUnstake),
-    CompleteUnstake(CompleteUnstake),
-    ClaimRewards(ClaimRewards),
+    SpotDeploy(SpotDeploy),
+    PerpDeploy(PerpDeploy),
+    CDeposit(CDeposit),
+    CWithdraw(CWithdraw),
+    TokenDelegate(TokenDelegate),
+    ConvertToMultiSigUser(ConvertToMultiSigUser),
+    MultiSig(MultiSig),
+    /// Does nothing but use up its nonce, invalidating any other action signed with it.
+    Noop,
+}
+
+impl Actions {
+    /// Connection id of an L1 action: keccak of the msgpack encoded action, nonce and vault.
+    pub(crate) fn hash(&self, nonce: u64, vault_address: Option<Address>) -> Result<B256> {
+        action_hash(self, nonce, vault_address)
+    }
+
+    /// Digest the signature of the action commits to when it is sent with `nonce` on behalf
+    /// of `vault_address`.
+    pub(crate) fn signing_hash(
+        &self,
+        nonce: u64,
+        vault_address: Option<Address>,
+        is_mainnet: bool,
+    ) -> Result<B256> {
+        if let Actions::MultiSig(multi_sig) = self {
+            return multi_sig.signing_hash(nonce, vault_address, is_mainnet);
+        }
+        match self.user_signed_type() {
+            Some((primary_type, fields, signature_chain_id)) => user_signed_action_hash(
+                &format!("{HYPERLIQUID_EIP_PREFIX}{primary_type}"),
+                fields,
+                &serde_json::to_value(self)?,
+                signature_chain_id,
+            ),
+            None => Ok(l1_action_signing_hash(
+                self.hash(nonce, vault_address)?,
+                is_mainnet,
+            )),
+        }
+    }
+
+    /// Digest the authorized users of `multi_sig_user` sign when the action is wrapped in a
+    /// multi-sig action submitted by `outer_signer`.
+    pub(crate) fn multi_sig_signing_hash(
+        &self,
+        multi_sig_user: Address,
+        outer_signer: Address,
+        nonce: u64,
+        vault_address: Option<Address>,
+        is_mainnet: bool,
+    ) -> Result<B256> {
+        let multi_sig_user = format!("{multi_sig_user:#x}");
+        let outer_signer = format!("{outer_signer:#x}");
+
+        let Some((primary_type, fields, signature_chain_id)) = self.user_signed_type() else {
+            let connection_id =
+                action_hash(&(multi_sig_user, outer_signer, self), nonce, vault_address)?;
+            return Ok(l1_action_signing_hash(connection_id, is_mainnet));
+        };
+
+        let mut message = serde_json::to_value(self)?;
+        message["payloadMultiSigUser"] = multi_sig_user.into();
+        message["outerSigner"] = outer_signer.into();
+        let mut multi_sig_fields = vec![
+            fields[0],
+            ("payloadMultiSigUser", "address"),
+            ("outerSigner", "address"),
+        ];
+        multi_sig_fields.extend_from_slice(&fields[1..]);
+        user_signed_action_hash(
+            &format!("{HYPERLIQUID_EIP_PREFIX}{primary_type}"),
+            &multi_sig_fields,
+            &message,
+            signature_chain_id,
+        )
+    }
+
+    /// EIP-712 primary type, fields and signature chain id of user-signed actions, `None` for
+    /// L1 actions.
+    pub(crate) fn user_signed_type(&self) -> Option<UserSignedType> {
+        fn of<T: UserSignedAction>(action: &T) -> UserSignedType {
+            (T::PRIMARY_TYPE, T::FIELDS, action.signature_chain_id())
+        }
+
+        match self {
+            Actions::UsdSend(action) => Some(of(action)),
+            Actions::Withdraw3(action) => Some(of(action)),
+            Actions::SpotSend(action) => Some(of(action)),
+            Actions::ApproveBuilderFee(action) => Some(of(action)),
+            Actions::CDeposit(action) => Some(of(action)),
+            Actions::CWithdraw(action) => Some(of(action)),
+            Actions::TokenDelegate(action) => Some(of(action)),
+            Actions::ConvertToMultiSigUser(action) => Some(of(action)),
+            _ => None,
+        }
+    }
+
+    /// Nonce a user-signed action carries itself, which must match the nonce it is sent with.
+    /// Transfers call it `time`.
+    pub(crate) fn user_signed_nonce(&self) -> Option<u64> {
+        self.user_signed_type()?;
+        let action = serde_json::to_value(self).ok()?;
+        action
+            .get("nonce")
+            .or_else(|| action.get("time"))
+            .and_then(|nonce| nonce.as_u64())
+    }
+}
+
+/// `type` tag, EIP-712 primary type and fields of every user-signed action, including ones the
+/// SDK doesn't send itself, so that payloads signed elsewhere can be verified.
+pub(crate) const USER_SIGNED_TYPES: &[(&str, &str, Eip712Fields)] = &[
+    ("usdSend", UsdSend::PRIMARY_TYPE, UsdSend::FIELDS),
+    ("withdraw3", Withdraw3::PRIMARY_TYPE, Withdraw3::FIELDS),
+    ("spotSend", SpotSend::PRIMARY_TYPE, SpotSend::FIELDS),
+    (
+        "usdClassTransfer",
+        "UsdClassTransfer",
+        &[
+            ("hyperliquidChain", "string"),
+            ("amount", "string"),
+            ("toPerp", "bool"),
+            ("nonce", "uint64"),
+        ],
+    ),
+    (
+        "approveAgent",
+        "ApproveAgent",
+        &[
+            ("hyperliquidChain", "string"),
+            ("agentAddress", "address"),
+            ("agentName", "string"),
+            ("nonce", "uint64"),
+        ],
+    ),
+    ("approveBuilderFee", ApproveBuilderFee::PRIMARY_TYPE, ApproveBuilderFee::FIELDS),
+    ("cDeposit", CDeposit::PRIMARY_TYPE, CDeposit::FIELDS),
+    ("cWithdraw", CWithdraw::PRIMARY_TYPE, CWithdraw::FIELDS),
+    ("tokenDelegate", TokenDelegate::PRIMARY_TYPE, TokenDelegate::FIELDS),
+    (
+        "convertToMultiSigUser",
+        ConvertToMultiSigUser::PRIMARY_TYPE,
+        ConvertToMultiSigUser::FIELDS,
+    ),
+    (
+        "sendAsset",
+        "SendAsset",
+        &[
+            ("hyperliquidChain", "string"),
+            ("destination", "string"),
+            ("sourceDex", "string"),
+            ("destinationDex", "string"),
+            ("token", "string"),
+            ("amount", "string"),
+            ("fromSubAccount", "string"),
+            ("nonce", "uint64"),
+        ],
+    ),
+    (
+        "userDexAbstraction",
+        "UserDexAbstraction",
+        &[
+            ("hyperliquidChain", "string"),
+            ("user", "address"),
+            ("enabled", "bool"),
+            ("nonce", "uint64"),
+        ],
+    ),
+];
+
+pub(crate) type Eip712Fields = &'static [(&'static str, &'static str)];
+
+pub(crate) type UserSignedType = (
+    &'static str,
+    Eip712Fields,
+    U256,
+);
+
+/// Keccak of the msgpack encoded `action`, nonce and vault, which L1 signatures commit to.
+pub(crate) fn action_hash<T: Serialize>(
+    action: &T,
+    nonce: u64,
+    vault_address: Option<Address>,
+) -> Result<B256> {
+    expiring_action_hash(action, nonce, vault_address, None)
+}
+
+/// [`action_hash`] of an action that the exchange rejects after `expires_after`.
+pub(crate) fn expiring_action_hash<T: Serialize>(
+    action: &T,
+    nonce: u64,
+    vault_address: Option<Address>,
+    expires_after: Option<u64>,
+) -> Result<B256> {
+    // Hashed as sent: msgpack encodes typed fields such as `U256` as bytes rather than the
+    // hex strings they are posted as.
+    let json = serde_json::to_string(action).map_err(|e| Error::JsonParse(e.to_string()))?;
+    let action: OrderedJson =
+        serde_json::from_str(&json).map_err(|e| Error::JsonParse(e.to_string()))?;
+    let mut bytes =
+        rmp_serde::to_vec_named(&action).map_err(|e| Error::RmpParse(e.to_string()))?;
+    bytes.extend(nonce.to_be_bytes());
+    if let Some(vault_address) = vault_address {
+        bytes.push(1);
+        bytes.extend(vault_address.as_slice());
+    } else {
+        bytes.push(0);
+    }
+    if let Some(expires_after) = expires_after {
+        bytes.push(0);
+        bytes.extend(expires_after.to_be_bytes());
+    }
+    Ok(keccak256(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::{
+            order::{Limit, Order},
+            perp_deploy::{PerpAssetRequest, RegisterAsset, SetOracle},
+            spot_deploy::{Genesis, RegisterToken, TokenSpec, UserGenesis},
+        },
+        signature::{l1_action_signing_hash, sign_l1_action},
+        LocalWallet,
+    };
+
+    fn get_wallet() -> Result<LocalWallet> {
+        let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+        priv_key
+            .parse::<LocalWallet>()
+            .map_err(|e| Error::PrivateKeyParse(e.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::BulkOrder(BulkOrder {
+            orders: vec![OrderRequest {
+                asset: 1,
+                is_buy: true,
+                limit_px: "2000.0".to_string(),
+                sz: "3.5".to_string(),
+                reduce_only: false,
+                order_type: Order::Limit(Limit {
+                    tif: "Ioc".to_string(),
+                }),
+                cloid: None,
+            }],
+            grouping: "na".to_string(),
+            builder: None,
+        });
+        let connection_id = action.hash(1583838, None)?;
+
+        let signature = sign_l1_action(l1_action_signing_hash(connection_id, true), &wallet).await?;
+        let expected_mainnet_sig = "77957e58e70f43b6b68581f2dc42011fc384538a2e5b7bf42d5b936f19fbb67360721a8598727230f67080efee48c812a6a4442013fd3b0eed509171bef9f23f1c";
+        assert_eq!(signature.to_string(), expected_mainnet_sig);
+        Ok(())
+    }
+
+    #[test]
+    fn test_order_action_serialization_with_builder() -> Result<()> {
+        let action = Actions::BulkOrder(BulkOrder {
+            orders: vec![],
+            grouping: "na".to_string(),
+            builder: Some(BuilderInfo {
+                builder: "0x1962905b0a2d0ce7907ae1a0d17f3e4a1f63dfb7".to_string(),
+                fee: 10,
+            }),
+        });
+        assert_eq!(
+            serde_json::to_value(&action)?,
+            serde_json::json!({
+                "type": "order",
+                "orders": [],
+                "grouping": "na",
+                "builder": {"b": "0x1962905b0a2d0ce7907ae1a0d17f3e4a1f63dfb7", "f": 10},
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_spot_deploy_action_serialization() -> Result<()> {
+        let action = Actions::SpotDeploy(SpotDeploy::RegisterToken(RegisterToken {
+            spec: TokenSpec {
+                name: "TEST0".to_string(),
+                sz_decimals: 2,
+                wei_decimals: 8,
+            },
+            max_gas: 1000000,
+            full_name: "Test token".to_string(),
+        }));
+        assert_eq!(
+            serde_json::to_value(&action)?,
+            serde_json::json!({
+                "type": "spotDeploy",
+                "registerToken2": {
+                    "spec": {"name": "TEST0", "szDecimals": 2, "weiDecimals": 8},
+                    "maxGas": 1000000,
+                    "fullName": "Test token",
+                },
+            })
+        );
+
+        let action = Actions::SpotDeploy(SpotDeploy::UserGenesis(UserGenesis {
+            token: 1,
+            user_and_wei: vec![(
+                "0x0d1d9635d0640821d15e323ac8adadfa9c111414".to_string(),
+                "100000".to_string(),
+            )],
+            existing_token_and_wei: vec![(0, "5".to_string())],
+        }));
+        assert_eq!(
+            serde_json::to_value(&action)?,
+            serde_json::json!({
+                "type": "spotDeploy",
+                "userGenesis": {
+                    "token": 1,
+                    "userAndWei": [["0x0d1d9635d0640821d15e323ac8adadfa9c111414", "100000"]],
+                    "existingTokenAndWei": [[0, "5"]],
+                },
+            })
+        );
+
+        let action = Actions::SpotDeploy(SpotDeploy::Genesis(Genesis {
+            token: 1,
+            max_supply: "1000000".to_string(),
+            no_hyperliquidity: None,
+        }));
+        let expected =
+            serde_json::json!({"type": "spotDeploy", "genesis": {"token": 1, "maxSupply": "1000000"}});
+        assert_eq!(serde_json::to_value(&action)?, expected);
+
+        // The hashed msgpack encoding has to carry the same nesting as the JSON that is posted
+        let packed = rmp_serde::to_vec_named(&action).map_err(|e| Error::RmpParse(e.to_string()))?;
+        let unpacked: serde_json::Value =
+            rmp_serde::from_slice(&packed).map_err(|e| Error::RmpParse(e.to_string()))?;
+        assert_eq!(unpacked, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_perp_deploy_action_serialization() -> Result<()> {
+        let action = Actions::PerpDeploy(PerpDeploy::RegisterAsset(RegisterAsset {
+            max_gas: None,
+            asset_request: PerpAssetRequest {
+                coin: "test:ABC".to_string(),
+                sz_decimals: 2,
+                oracle_px: "10.0".to_string(),
+                margin_table_id: 10,
+                only_isolated: false,
+            },
+            dex: "test".to_string(),
+            schema: None,
+        }));
+        let expected = serde_json::json!({
+            "type": "perpDeploy",
+            "registerAsset": {
+                "maxGas": null,
+                "assetRequest": {
+                    "coin": "test:ABC",
+                    "szDecimals": 2,
+                    "oraclePx": "10.0",
+                    "marginTableId": 10,
+                    "onlyIsolated": false,
+                },
+                "dex": "test",
+                "schema": null,
+            },
+        });
+        assert_eq!(serde_json::to_value(&action)?, expected);
+        let packed = rmp_serde::to_vec_named(&action).map_err(|e| Error::RmpParse(e.to_string()))?;
+        let unpacked: serde_json::Value =
+            rmp_serde::from_slice(&packed).map_err(|e| Error::RmpParse(e.to_string()))?;
+        assert_eq!(unpacked, expected);
+
+        let action = Actions::PerpDeploy(PerpDeploy::SetOracle(SetOracle {
+            dex: "test".to_string(),
+            oracle_pxs: vec![("test:ABC".to_string(), "10.1".to_string())],
+            mark_pxs: vec![vec![("test:ABC".to_string(), "10.2".to_string())]],
+            external_perp_pxs: vec![],
+        }));
+        assert_eq!(
+            serde_json::to_value(&action)?,
+            serde_json::json!({
+                "type": "perpDeploy",
+                "setOracle": {
+                    "dex": "test",
+                    "oraclePxs": [["test:ABC", "10.1"]],
+                    "markPxs": [[["test:ABC", "10.2"]]],
+                    "externalPerpPxs": [],
+                },
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_vault_action_serialization() -> Result<()> {
+        let action = Actions::VaultModify(VaultModify {
+            vault_address: "0x1719884eb866cb12b2287399b15f7db5e7d775ea".to_string(),
+            allow_deposits: Some(false),
+            always_close_on_withdraw: None,
+        });
+        let expected = serde_json::json!({
+            "type": "vaultModify",
+            "vaultAddress": "0x1719884eb866cb12b2287399b15f7db5e7d775ea",
+            "allowDeposits": false,
+            "alwaysCloseOnWithdraw": null,
+        });
+        assert_eq!(serde_json::to_value(&action)?, expected);
+        let packed = rmp_serde::to_vec_named(&action).map_err(|e| Error::RmpParse(e.to_string()))?;
+        let unpacked: serde_json::Value =
+            rmp_serde::from_slice(&packed).map_err(|e| Error::RmpParse(e.to_string()))?;
+        assert_eq!(unpacked, expected);
+
+        let action = Actions::CreateVault(CreateVault {
+            name: "Test vault".to_string(),
+            description: "A vault".to_string(),
+            initial_usd: 100_000_000,
+            nonce: 1700000000000,
+        });
+        assert_eq!(
+            serde_json::to_value(&action)?,
+            serde_json::json!({
+                "type": "createVault",
+                "name": "Test vault",
+                "description": "A vault",
+                "initialUsd": 100000000,
+                "nonce": 1700000000000u64,
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_noop_serialization() -> Result<()> {
+        let expected = serde_json::json!({"type": "noop"});
+        assert_eq!(serde_json::to_value(Actions::Noop)?, expected);
+        let packed =
+            rmp_serde::to_vec_named(&Actions::Noop).map_err(|e| Error::RmpParse(e.to_string()))?;
+        let unpacked: serde_json::Value =
+            rmp_serde::from_slice(&packed).map_err(|e| Error::RmpParse(e.to_string()))?;
+        assert_eq!(unpacked, expected);
+        assert!(matches!(serde_json::from_value(expected)?, Actions::Noop));
+        Ok(())
+    }
 }
@@ -1,131 +1,281 @@
 use alloy_primitives::{Address, U256};
+use chrono::Utc;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    sync::Arc,
+};
 
 use crate::{
-    constants::Chain,
+    constants::{Chain, ARBITRUM_TESTNET_CHAIN_ID, HYPE_TOKEN_NAME, USD_DECIMALS},
     errors::{HyperliquidError, Result},
     exchange::{
         actions::{
-            ApproveAgent, ApproveBuilderFee, BulkCancel, BulkCancelCloid, BulkModify, BulkOrder,
-            ClassTransfer, ClaimRewards, CompleteUnstake, SetReferrer, SpotSend, Stake,
-            StartUnstake, UpdateIsolatedMargin, UpdateLeverage, UsdSend, VaultTransfer, Withdraw3,
+            Actions, ApproveAgent, ApproveBuilderFee, BulkCancel, BulkCancelCloid, BulkModify,
+            BulkOrder, CDeposit, CWithdraw, ClassTransfer, ConvertToMultiSigUser, CreateVault,
+            EvmUserModify, RegisterReferrer, SetReferrer,
+            SpotSend, TokenDelegate, UpdateIsolatedMargin, UpdateLeverage, UsdSend,
+            UserSignedAction, VaultDistribute, VaultModify, VaultTransfer, Withdraw3,
         },
         cancel::{CancelRequest, CancelRequestCloid, ClientCancelRequest, ClientCancelRequestCloid},
+        exchange_responses::{ExchangeResponseStatus, NonceBurnReport, TransferStatus},
         modify::ModifyRequest,
-        order::{ClientOrder, Limit, Order, OrderRequest, Trigger, ClientOrderRequest},
-        BuilderInfo,
-    },
-    info::sub_structs::OrderInfo,
-    helpers::*,
-    signature::{
-        agent::Agent,
-        create_signature::sign_l1_action,
+        order::{ClientOrder, ClientOrderRequest, OrderRequest},
+        perp_deploy::{PerpAssetRequest, PerpDeploy, PerpDexSchema, RegisterAsset, SetOracle},
+        spot_deploy::{
+            Genesis, RegisterHyperliquidity, RegisterSpot, RegisterToken,
+            SetDeployerTradingFeeShare, SpotDeploy, TokenSpec, UserGenesis,
+        },
+        BuilderInfo, ExchangePayload, MultiSigSigners, MultiSigTransaction, SigningEnvelope,
     },
+    helpers::{decimal_to_wei, float_to_string_for_hashing, round_px, round_sz, NonceManager},
+    signature::{l1_action_signing_hash, sign_l1_action},
+    signature::create_signature::SignatureRsv,
+    meta::{is_spot_asset, perp_dex_offset, TokenInfo},
+    BaseUrl, InfoClient, LocalWallet,
 };
 
-impl From<ClientOrderRequest> for OrderRequest {
-    fn from(req: ClientOrderRequest) -> Self {
-        OrderRequest {
-            asset: req.asset.parse().unwrap_or_default(),
-            is_buy: req.is_buy,
-            reduce_only: req.reduce_only,
-            limit_px: req.limit_px.to_string(),
-            sz: req.sz.to_string(),
-            cloid: req.cloid.map(|uuid| uuid.to_string()),
-            order_type: match req.order_type {
-                ClientOrder::Limit(limit) => Order::Limit(Limit {
-                    tif: limit.tif,
-                }),
-                ClientOrder::Trigger(trigger) => Order::Trigger(Trigger {
-                    is_market: trigger.is_market,
-                    trigger_px: trigger.trigger_px.to_string(),
-                    tpsl: trigger.tpsl,
-                }),
-            },
-        }
-    }
+#[derive(Debug)]
+pub struct ExchangeClient {
+    http_client: Client,
+    base_url: String,
+    wallet: Option<LocalWallet>,
+    vault_address: Option<Address>,
+    coin_to_asset: HashMap<String, u32>,
+    sz_decimals: HashMap<u32, u32>,
+    spot_tokens: HashMap<String, TokenInfo>,
+    nonce_manager: Arc<NonceManager>,
 }
 
-impl From<ClientCancelRequest> for CancelRequest {
-    fn from(req: ClientCancelRequest) -> Self {
-        CancelRequest {
-            asset: req.asset.parse().unwrap_or_default(),
-            oid: req.oid,
+impl ExchangeClient {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            http_client: Client::new(),
+            base_url,
+            wallet: None,
+            vault_address: None,
+            coin_to_asset: HashMap::new(),
+            sz_decimals: HashMap::new(),
+            spot_tokens: HashMap::new(),
+            nonce_manager: Arc::default(),
         }
     }
-}
 
-impl From<ClientCancelRequestCloid> for CancelRequestCloid {
-    fn from(req: ClientCancelRequestCloid) -> Self {
-        CancelRequestCloid {
-            asset: req.asset.parse().unwrap_or_default(),
-            cloid: req.cloid,
+    /// Creates a client that signs actions with `wallet`, trading on behalf of `vault_address`
+    /// when one is given. Perp and spot asset indices are resolved from the venue's meta on
+    /// construction, so spot pairs can be traded by name (`PURR/USDC`) or index (`@1`).
+    pub async fn new_with_wallet(
+        base_url: String,
+        wallet: LocalWallet,
+        vault_address: Option<Address>,
+    ) -> Result<Self> {
+        let info_client = InfoClient::new(base_url.clone());
+        let (meta, spot_meta) =
+            tokio::try_join!(info_client.get_meta(None), info_client.get_spot_meta())?;
+
+        let mut coin_to_asset = HashMap::new();
+        let mut sz_decimals = HashMap::new();
+        meta.add_to_index_maps("", 0, &mut coin_to_asset, &mut sz_decimals);
+        let coin_to_asset = spot_meta.add_pair_and_name_to_index_map(coin_to_asset);
+        sz_decimals.extend(spot_meta.asset_sz_decimals());
+        let spot_tokens = spot_meta
+            .tokens
+            .into_iter()
+            .map(|token| (token.name.clone(), token))
+            .collect();
+
+        Ok(Self {
+            http_client: Client::new(),
+            base_url,
+            wallet: Some(wallet),
+            vault_address,
+            coin_to_asset,
+            sz_decimals,
+            spot_tokens,
+            nonce_manager: Arc::default(),
+        })
+    }
+
+    /// Takes nonces from `nonce_manager`, e.g. one shared by all clients signing with the same
+    /// wallet.
+    pub fn with_nonce_manager(mut self, nonce_manager: Arc<NonceManager>) -> Self {
+        self.nonce_manager = nonce_manager;
+        self
+    }
+
+    /// The nonce manager that hands out the nonces of every action the client signs.
+    pub fn nonce_manager(&self) -> &Arc<NonceManager> {
+        &self.nonce_manager
+    }
+
+    /// Makes the coins of the builder-deployed perp DEX `dex` tradable as `dex:COIN`.
+    pub async fn add_perp_dex(&mut self, dex: &str) -> Result<()> {
+        let info_client = InfoClient::new(self.base_url.clone());
+        let dexs = info_client.perp_dexs().await?;
+        let dex_index = dexs
+            .iter()
+            .position(|perp_dex| perp_dex.as_ref().is_some_and(|perp_dex| perp_dex.name == dex))
+            .ok_or_else(|| HyperliquidError::InvalidParameter(format!("unknown perp dex {dex}")))?;
+
+        let meta = info_client.get_meta(Some(dex.to_string())).await?;
+        meta.add_to_index_maps(
+            dex,
+            perp_dex_offset(dex_index),
+            &mut self.coin_to_asset,
+            &mut self.sz_decimals,
+        );
+        Ok(())
+    }
+
+    fn wallet(&self) -> Result<&LocalWallet> {
+        self.wallet.as_ref().ok_or_else(|| {
+            HyperliquidError::AuthenticationError("exchange client has no wallet".to_string())
+        })
+    }
+
+    fn is_mainnet(&self) -> bool {
+        self.base_url == BaseUrl::Mainnet.get_url()
+    }
+
+    fn chain(&self) -> Chain {
+        if self.is_mainnet() {
+            Chain::Mainnet
+        } else {
+            Chain::Testnet
         }
     }
-}
 
-pub(crate) enum Actions {
-    ApproveBuilderFee(ApproveBuilderFee),
-    ApproveAgent(ApproveAgent),
-    UsdSend(UsdSend),
-    SpotSend(SpotSend),
-    ClassTransfer(ClassTransfer),
-    SetReferrer(SetReferrer),
-    UpdateLeverage(UpdateLeverage),
-    UpdateIsolatedMargin(UpdateIsolatedMargin),
-    BulkOrder(BulkOrder),
-    BulkCancel(BulkCancel),
-    BulkModify(BulkModify),
-    BulkCancelCloid(BulkCancelCloid),
-    Withdraw3(Withdraw3),
-    Stake(Stake),
-    StartUnstake(StartUnstake),
-    CompleteUnstake(CompleteUnstake),
-    ClaimRewards(ClaimRewards),
-    VaultTransfer(VaultTransfer),
+    fn hyperliquid_chain(&self) -> String {
+        self.chain().hyperliquid_chain().to_string()
+    }
 
-}
+    /// Chain id user-signed actions are signed for; the venue accepts the same one everywhere.
+    fn signature_chain_id(&self) -> U256 {
+        U256::from(ARBITRUM_TESTNET_CHAIN_ID)
+    }
 
-#[derive(Debug, Deserialize)]
-#[serde(tag = "type")]
-pub enum ExchangeResponseStatus {
-    Ok(ExchangeResponse),
-    Err(String),
-}
+    fn asset_index(&self, asset: &str) -> Result<u32> {
+        self.coin_to_asset
+            .get(asset)
+            .copied()
+            .ok_or_else(|| HyperliquidError::InvalidAsset(asset.to_string()))
+    }
 
-#[derive(Debug, Deserialize)]
-pub struct ExchangeResponse {
-    pub status: String,
-    pub data: Option<ExchangeData>,
-}
+    /// Resolves a spot token given by name or as `NAME:0xtokenId`.
+    fn spot_token(&self, token: &str) -> Result<&TokenInfo> {
+        let name = token.split_once(':').map_or(token, |(name, _)| name);
+        self.spot_tokens
+            .get(name)
+            .filter(|info| name == token || info.wire_name() == token)
+            .ok_or_else(|| HyperliquidError::InvalidAsset(token.to_string()))
+    }
 
-#[derive(Debug, Deserialize)]
-pub struct ExchangeData {
-    pub statuses: Vec<ExchangeDataStatus>,
-}
+    async fn post_action(
+        &self,
+        action: Actions,
+        signature: SignatureRsv,
+        nonce: u64,
+        vault_address: Option<Address>,
+    ) -> Result<ExchangeResponseStatus> {
+        let payload = ExchangePayload::new(&action, signature, nonce, vault_address)?;
+        let req_json = serde_json::to_string(&payload)
+            .map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
+        let response = self
+            .http_client
+            .post(format!("{}/exchange", self.base_url))
+            .header("Content-Type", "application/json")
+            .body(req_json)
+            .send()
+            .await
+            .map_err(HyperliquidError::from)?;
+        let text = response.text().await.map_err(HyperliquidError::from)?;
+        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
+    }
 
-#[derive(Debug, Deserialize, Clone)]
-#[serde(tag = "type")]
-pub enum ExchangeDataStatus {
-    Success,
-    Error(String),
-    Filled(OrderInfo),
-    Resting(OrderInfo),
-}
+    async fn sign_and_post_l1_action(&self, action: Actions) -> Result<ExchangeResponseStatus> {
+        self.sign_and_post_l1_action_with_vault(action, self.vault_address)
+            .await
+    }
 
-#[derive(Debug)]
-pub struct ExchangeClient {
-    http_client: Client,
-    base_url: String,
-}
+    /// Signs `action` on behalf of `vault_address` rather than the client's vault; vault
+    /// management is always signed by the leader itself.
+    async fn sign_and_post_l1_action_with_vault(
+        &self,
+        action: Actions,
+        vault_address: Option<Address>,
+    ) -> Result<ExchangeResponseStatus> {
+        let nonce = self.nonce_manager.next_nonce();
+        self.sign_and_post_l1_action_with_nonce(action, nonce, vault_address)
+            .await
+    }
 
-impl ExchangeClient {
-    pub fn new(base_url: String) -> Self {
-        Self {
-            http_client: Client::new(),
-            base_url,
+    async fn sign_and_post_l1_action_with_nonce(
+        &self,
+        action: Actions,
+        nonce: u64,
+        vault_address: Option<Address>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = self.wallet()?;
+        let connection_id = action.hash(nonce, vault_address)?;
+        let signature = sign_l1_action(
+            l1_action_signing_hash(connection_id, self.is_mainnet()),
+            wallet,
+        )
+        .await?;
+
+        self.post_action(action, signature.to_rsv(), nonce, vault_address)
+            .await
+    }
+
+    async fn sign_and_post_user_action<T: UserSignedAction>(
+        &self,
+        action: T,
+        nonce: u64,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = self.wallet()?;
+        let signature = sign_l1_action(action.signing_hash()?, wallet).await?;
+
+        self.post_action(action.into(), signature.to_rsv(), nonce, None)
+            .await
+    }
+
+    /// Rounds the order's prices and size to the asset's decimal rules before converting it to
+    /// its wire form.
+    fn convert_order(&self, mut order: ClientOrderRequest) -> Result<OrderRequest> {
+        let asset = self.asset_index(&order.asset)?;
+        if let Some(&sz_decimals) = self.sz_decimals.get(&asset) {
+            let is_spot = is_spot_asset(asset);
+            order.sz = round_sz(order.sz, sz_decimals);
+            order.limit_px = round_px(order.limit_px, sz_decimals, is_spot);
+            if let ClientOrder::Trigger(trigger) = &mut order.order_type {
+                trigger.trigger_px = round_px(trigger.trigger_px, sz_decimals, is_spot);
+            }
+            if order.sz <= 0.0 {
+                return Err(HyperliquidError::InvalidSize(format!(
+                    "size rounds to zero at {sz_decimals} decimals for {}",
+                    order.asset
+                )));
+            }
         }
+        Ok(order.convert(&self.coin_to_asset)?)
+    }
+
+    fn convert_builder(builder: Option<BuilderInfo>) -> Result<Option<BuilderInfo>> {
+        builder
+            .map(|builder| {
+                let address = builder.builder.parse::<Address>().map_err(|e| {
+                    HyperliquidError::InvalidParameter(format!(
+                        "invalid builder address {}: {e}",
+                        builder.builder
+                    ))
+                })?;
+                Ok(BuilderInfo {
+                    builder: format!("{address:#x}"),
+                    fee: builder.fee,
+                })
+            })
+            .transpose()
     }
 
     pub fn get_timestamp(&self) -> U256 {
@@ -135,23 +285,18 @@ impl ExchangeClient {
             .as_secs())
     }
 
-    pub async fn usd_send(&self, destination: Address, amount: U256, hyperliquid_chain: String) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = UsdSend {
-            signatureChainId: U256::from(421614u64),
-            hyperliquidChain: hyperliquid_chain,
-            destination,
-            amount,
-            time: timestamp,
+    /// Sends `amount` USDC, a decimal string such as `"12.5"`, from the perp balance to
+    /// `destination`.
+    pub async fn usd_send(&self, destination: Address, amount: &str) -> Result<TransferStatus> {
+        let time = self.nonce_manager.next_nonce();
+        let action = UsdSend {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            destination: format!("{destination:#x}"),
+            amount: transfer_amount(amount, USD_DECIMALS)?,
+            time,
         };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/usdTransfer", self.base_url))
-            .body(req_json)
-            .send()
-            .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
+        Ok(self.sign_and_post_user_action(action, time).await?.into())
     }
 
     pub async fn approve_agent(&self, address: Address, hyperliquid_chain: String) -> Result<()> {
@@ -172,43 +317,64 @@ impl ExchangeClient {
         Ok(())
     }
 
-    pub async fn withdraw(&self, destination: Address, amount: U256, hyperliquid_chain: String) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = Withdraw3 {
-            signatureChainId: U256::from(421614u64),
-            hyperliquidChain: hyperliquid_chain,
-            destination,
-            amount,
-            time: timestamp,
+    /// Withdraws `amount` USDC, a decimal string, from the perp balance to `destination` on
+    /// Arbitrum. The bridge deducts its withdrawal fee from the amount.
+    pub async fn withdraw(&self, destination: Address, amount: &str) -> Result<TransferStatus> {
+        let time = self.nonce_manager.next_nonce();
+        let action = Withdraw3 {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            destination: format!("{destination:#x}"),
+            amount: transfer_amount(amount, USD_DECIMALS)?,
+            time,
         };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/withdraw", self.base_url))
-            .body(req_json)
-            .send()
+        Ok(self.sign_and_post_user_action(action, time).await?.into())
+    }
+
+    /// Sends `amount`, a decimal string, of the spot `token` to `destination`. The token is
+    /// given by name or as `NAME:0xtokenId` and the amount may not have more decimals than
+    /// the token's wei decimals, which can be more than its size decimals.
+    pub async fn spot_send(
+        &self,
+        destination: Address,
+        token: &str,
+        amount: &str,
+    ) -> Result<TransferStatus> {
+        self.send_spot_token(destination, self.spot_token(token)?, amount)
             .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
     }
 
-    pub async fn spot_send(&self, destination: Address, token: String, amount: U256, hyperliquid_chain: String) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = SpotSend {
-            signatureChainId: U256::from(421614u64),
-            hyperliquidChain: hyperliquid_chain,
-            destination,
-            amount,
-            token,
-            time: timestamp,
-        };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/spotTransfer", self.base_url))
-            .body(req_json)
-            .send()
+    /// Moves `amount`, a decimal string, of the spot `token` from HyperCore to the wallet's
+    /// address on HyperEVM by sending it to the token's system address. Apart from HYPE, the
+    /// native gas token, only tokens linked to an EVM contract can be moved.
+    pub async fn transfer_to_evm(&self, token: &str, amount: &str) -> Result<TransferStatus> {
+        let token = self.spot_token(token)?;
+        if token.name != HYPE_TOKEN_NAME && token.evm_contract.is_none() {
+            return Err(HyperliquidError::InvalidAsset(format!(
+                "{} is not linked to an EVM contract",
+                token.name
+            )));
+        }
+        self.send_spot_token(token.system_address(), token, amount)
             .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
+    }
+
+    async fn send_spot_token(
+        &self,
+        destination: Address,
+        token: &TokenInfo,
+        amount: &str,
+    ) -> Result<TransferStatus> {
+        let time = self.nonce_manager.next_nonce();
+        let action = SpotSend {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            destination: format!("{destination:#x}"),
+            token: token.wire_name(),
+            amount: transfer_amount(amount, token.wei_decimals as u32)?,
+            time,
+        };
+        Ok(self.sign_and_post_user_action(action, time).await?.into())
     }
 
     pub async fn class_transfer(&self, amount: U256, to_perp: bool, hyperliquid_chain: String) -> Result<()> {
@@ -230,70 +396,133 @@ impl ExchangeClient {
         Ok(())
     }
 
-    pub async fn cancel(&self, req: ClientCancelRequest, _builder: Option<BuilderInfo>) -> Result<ExchangeResponseStatus> {
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        let response = self.http_client
-            .post(format!("{}/exchange/cancel", self.base_url))
-            .body(req_json)
-            .send()
-            .await
-            .map_err(HyperliquidError::from)?;
-        let text = response.text().await.map_err(HyperliquidError::from)?;
-        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
+    pub async fn cancel(&self, req: ClientCancelRequest) -> Result<ExchangeResponseStatus> {
+        self.bulk_cancel(vec![req]).await
     }
 
-    pub async fn order(&self, req: ClientOrderRequest, _builder: Option<BuilderInfo>) -> Result<ExchangeResponseStatus> {
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        let response = self.http_client
-            .post(format!("{}/exchange/order", self.base_url))
-            .body(req_json)
-            .send()
-            .await
-            .map_err(HyperliquidError::from)?;
-        let text = response.text().await.map_err(HyperliquidError::from)?;
-        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
+    pub async fn order(
+        &self,
+        req: ClientOrderRequest,
+        builder: Option<BuilderInfo>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_order(vec![req], "na".to_string(), builder).await
     }
 
-    pub async fn set_referrer(&self, code: String) -> Result<()> {
-        let req = serde_json::json!({
-            "code": code
-        });
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/setReferrer", self.base_url))
-            .body(req_json)
-            .send()
-            .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
+    /// Approves `builder` to charge up to `max_fee_rate` (e.g. `"0.01%"`) on orders routed with
+    /// its builder code.
+    pub async fn approve_builder_fee(
+        &self,
+        builder: Address,
+        max_fee_rate: String,
+    ) -> Result<ExchangeResponseStatus> {
+        let nonce = self.nonce_manager.next_nonce();
+        let action = ApproveBuilderFee {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            max_fee_rate,
+            builder: format!("{builder:#x}"),
+            nonce,
+        };
+        self.sign_and_post_user_action(action, nonce).await
     }
 
-    pub async fn cancel_by_cloid(&self, req: ClientCancelRequestCloid, _builder: Option<BuilderInfo>) -> Result<ExchangeResponseStatus> {
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        let response = self.http_client
-            .post(format!("{}/exchange/cancelByCloid", self.base_url))
-            .body(req_json)
-            .send()
+    /// Sets the referral code the wallet was referred with.
+    pub async fn set_referrer(&self, code: String) -> Result<ExchangeResponseStatus> {
+        let action = Actions::SetReferrer(SetReferrer { code });
+        self.sign_and_post_l1_action_with_vault(action, None).await
+    }
+
+    /// Creates the wallet's own referral code, once its referrer stage allows it.
+    pub async fn register_referrer(&self, code: String) -> Result<ExchangeResponseStatus> {
+        let action = Actions::RegisterReferrer(RegisterReferrer { code });
+        self.sign_and_post_l1_action_with_vault(action, None).await
+    }
+
+    /// Claims the wallet's referral rewards. Fails without signing when there is nothing to
+    /// claim.
+    pub async fn claim_referral_rewards(&self) -> Result<ExchangeResponseStatus> {
+        let info_client = InfoClient::new(self.base_url.clone());
+        let state = info_client
+            .query_referral_state(self.wallet()?.address())
+            .await?;
+        let unclaimed: f64 = state.unclaimed_rewards.parse().map_err(|_| {
+            HyperliquidError::InvalidResponse(format!(
+                "invalid unclaimed rewards {}",
+                state.unclaimed_rewards
+            ))
+        })?;
+        if unclaimed <= 0.0 {
+            return Err(HyperliquidError::InsufficientFunds(
+                "no unclaimed referral rewards".to_string(),
+            ));
+        }
+        self.sign_and_post_l1_action_with_vault(Actions::ClaimRewards, None)
             .await
-            .map_err(HyperliquidError::from)?;
-        let text = response.text().await.map_err(HyperliquidError::from)?;
-        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
     }
 
-    pub async fn vault_transfer(&self, vault_address: Address, is_deposit: bool, usd: String, hyperliquid_chain: String) -> Result<()> {
-        let req = VaultTransfer {
-            vault_address,
+    pub async fn cancel_by_cloid(
+        &self,
+        req: ClientCancelRequestCloid,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_cancel_cloid(vec![req]).await
+    }
+
+    /// Deposits `usd` (in millionths of a USDC) into `vault`, or withdraws it when not
+    /// `is_deposit`.
+    pub async fn vault_transfer(
+        &self,
+        vault: Address,
+        is_deposit: bool,
+        usd: u64,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = Actions::VaultTransfer(VaultTransfer {
+            vault_address: format!("{vault:#x}"),
             is_deposit,
             usd,
-        };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/vaultTransfer", self.base_url))
-            .body(req_json)
-            .send()
-            .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
+        });
+        self.sign_and_post_l1_action_with_vault(action, None).await
+    }
+
+    /// Creates a vault led by the wallet, seeded with `initial_usd` (in millionths of a USDC).
+    pub async fn create_vault(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = Actions::CreateVault(CreateVault {
+            name,
+            description,
+            initial_usd,
+            nonce: self.nonce_manager.next_nonce(),
+        });
+        self.sign_and_post_l1_action_with_vault(action, None).await
+    }
+
+    /// Changes whether `vault` accepts deposits and whether withdrawals close the withdrawing
+    /// follower's share of positions. `None` leaves a setting unchanged.
+    pub async fn vault_modify(
+        &self,
+        vault: Address,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = Actions::VaultModify(VaultModify {
+            vault_address: format!("{vault:#x}"),
+            allow_deposits,
+            always_close_on_withdraw,
+        });
+        self.sign_and_post_l1_action_with_vault(action, None).await
+    }
+
+    /// Distributes `usd` (in millionths of a USDC) of `vault`'s equity to its followers pro
+    /// rata.
+    pub async fn vault_distribute(&self, vault: Address, usd: u64) -> Result<ExchangeResponseStatus> {
+        let action = Actions::VaultDistribute(VaultDistribute {
+            vault_address: format!("{vault:#x}"),
+            usd,
+        });
+        self.sign_and_post_l1_action_with_vault(action, None).await
     }
 
     pub async fn update_leverage(&self, asset: u32, is_cross: bool, leverage: u32) -> Result<()> {
@@ -312,36 +541,39 @@ impl ExchangeClient {
         Ok(())
     }
 
-    pub async fn bulk_order(&self, orders: Vec<ClientOrderRequest>, grouping: String, builder: Option<BuilderInfo>) -> Result<ExchangeResponseStatus> {
-        let orders: Vec<OrderRequest> = orders.into_iter().map(|o| o.into()).collect();
-        let req = BulkOrder {
+    pub async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: String,
+        builder: Option<BuilderInfo>,
+    ) -> Result<ExchangeResponseStatus> {
+        let orders = orders
+            .into_iter()
+            .map(|order| self.convert_order(order))
+            .collect::<Result<Vec<_>>>()?;
+        let action = Actions::BulkOrder(BulkOrder {
             orders,
             grouping,
-            builder,
-        };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        let response = self.http_client
-            .post(format!("{}/exchange/bulkOrder", self.base_url))
-            .body(req_json)
-            .send()
-            .await
-            .map_err(HyperliquidError::from)?;
-        let text = response.text().await.map_err(HyperliquidError::from)?;
-        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
+            builder: Self::convert_builder(builder)?,
+        });
+        self.sign_and_post_l1_action(action).await
     }
 
-    pub async fn bulk_cancel(&self, cancels: Vec<ClientCancelRequest>) -> Result<ExchangeResponseStatus> {
-        let cancels: Vec<CancelRequest> = cancels.into_iter().map(|c| c.into()).collect();
-        let req = BulkCancel { cancels };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        let response = self.http_client
-            .post(format!("{}/exchange/bulkCancel", self.base_url))
-            .body(req_json)
-            .send()
+    pub async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+    ) -> Result<ExchangeResponseStatus> {
+        let cancels = cancels
+            .into_iter()
+            .map(|cancel| {
+                Ok(CancelRequest {
+                    asset: self.asset_index(&cancel.asset)?,
+                    oid: cancel.oid,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.sign_and_post_l1_action(Actions::BulkCancel(BulkCancel { cancels }))
             .await
-            .map_err(HyperliquidError::from)?;
-        let text = response.text().await.map_err(HyperliquidError::from)?;
-        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
     }
 
     pub async fn bulk_modify(&self, modifies: Vec<ModifyRequest>) -> Result<ExchangeResponseStatus> {
@@ -357,18 +589,21 @@ impl ExchangeClient {
         serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
     }
 
-    pub async fn bulk_cancel_cloid(&self, cancels: Vec<ClientCancelRequestCloid>) -> Result<ExchangeResponseStatus> {
-        let cancels: Vec<CancelRequestCloid> = cancels.into_iter().map(|c| c.into()).collect();
-        let req = BulkCancelCloid { cancels };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        let response = self.http_client
-            .post(format!("{}/exchange/bulkCancelCloid", self.base_url))
-            .body(req_json)
-            .send()
+    pub async fn bulk_cancel_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+    ) -> Result<ExchangeResponseStatus> {
+        let cancels = cancels
+            .into_iter()
+            .map(|cancel| {
+                Ok(CancelRequestCloid {
+                    asset: self.asset_index(&cancel.asset)?,
+                    cloid: cancel.cloid,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.sign_and_post_l1_action(Actions::BulkCancelCloid(BulkCancelCloid { cancels }))
             .await
-            .map_err(HyperliquidError::from)?;
-        let text = response.text().await.map_err(HyperliquidError::from)?;
-        serde_json::from_str(&text).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
     }
 
     pub async fn update_isolated_margin(&self, asset: u32, is_buy: bool, ntli: i64) -> Result<()> {
@@ -387,170 +622,1085 @@ impl ExchangeClient {
         Ok(())
     }
 
-    pub async fn stake(&self, amount: U256, chain: Chain) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = Stake {
-            signatureChainId: U256::from(chain.chain_id()),
-            hyperliquidChain: chain.as_str().to_string(),
-            amount,
-            time: timestamp,
-        };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/stake", self.base_url))
-            .body(req_json)
-            .send()
+    /// Registers a new spot token, bidding at most `max_gas` in the deploy gas auction.
+    pub async fn spot_deploy_register_token(
+        &self,
+        token_name: String,
+        sz_decimals: u32,
+        wei_decimals: u32,
+        max_gas: u64,
+        full_name: String,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = SpotDeploy::RegisterToken(RegisterToken {
+            spec: TokenSpec {
+                name: token_name,
+                sz_decimals,
+                wei_decimals,
+            },
+            max_gas,
+            full_name,
+        });
+        self.sign_and_post_l1_action(Actions::SpotDeploy(action))
             .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
     }
 
-    pub async fn start_unstake(&self, amount: U256, chain: Chain) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = StartUnstake {
-            signatureChainId: U256::from(chain.chain_id()),
-            hyperliquidChain: chain.as_str().to_string(),
-            amount,
-            time: timestamp,
-        };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/startUnstake", self.base_url))
-            .body(req_json)
-            .send()
+    /// Assigns genesis balances of `token` to users and to holders of existing tokens. May be
+    /// called several times before [`ExchangeClient::spot_deploy_genesis`].
+    pub async fn spot_deploy_user_genesis(
+        &self,
+        token: u32,
+        user_and_wei: Vec<(Address, String)>,
+        existing_token_and_wei: Vec<(u32, String)>,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = SpotDeploy::UserGenesis(UserGenesis {
+            token,
+            user_and_wei: user_and_wei
+                .into_iter()
+                .map(|(user, wei)| (format!("{user:#x}"), wei))
+                .collect(),
+            existing_token_and_wei,
+        });
+        self.sign_and_post_l1_action(Actions::SpotDeploy(action))
             .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
     }
 
-    pub async fn complete_unstake(&self, chain: Chain) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = CompleteUnstake {
-            signatureChainId: U256::from(chain.chain_id()),
-            hyperliquidChain: chain.as_str().to_string(),
-            time: timestamp,
-        };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/completeUnstake", self.base_url))
-            .body(req_json)
-            .send()
+    pub async fn spot_deploy_genesis(
+        &self,
+        token: u32,
+        max_supply: String,
+        no_hyperliquidity: bool,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = SpotDeploy::Genesis(Genesis {
+            token,
+            max_supply,
+            no_hyperliquidity: no_hyperliquidity.then_some(true),
+        });
+        self.sign_and_post_l1_action(Actions::SpotDeploy(action))
             .await
-            .map_err(HyperliquidError::from)?;
-        Ok(())
     }
 
-    pub async fn claim_rewards(&self, chain: Chain) -> Result<()> {
-        let timestamp = self.get_timestamp();
-        let req = ClaimRewards {
-            signatureChainId: U256::from(chain.chain_id()),
-            hyperliquidChain: chain.as_str().to_string(),
-            time: timestamp,
+    pub async fn spot_deploy_register_spot(
+        &self,
+        base_token: u32,
+        quote_token: u32,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = SpotDeploy::RegisterSpot(RegisterSpot {
+            tokens: [base_token, quote_token],
+        });
+        self.sign_and_post_l1_action(Actions::SpotDeploy(action))
+            .await
+    }
+
+    pub async fn spot_deploy_register_hyperliquidity(
+        &self,
+        spot: u32,
+        start_px: f64,
+        order_sz: f64,
+        n_orders: u32,
+        n_seeded_levels: Option<u32>,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = SpotDeploy::RegisterHyperliquidity(RegisterHyperliquidity {
+            spot,
+            start_px: float_to_string_for_hashing(start_px),
+            order_sz: float_to_string_for_hashing(order_sz),
+            n_orders,
+            n_seeded_levels,
+        });
+        self.sign_and_post_l1_action(Actions::SpotDeploy(action))
+            .await
+    }
+
+    /// Sets the share of trading fees paid to the deployer of `token`, e.g. `"50%"`.
+    pub async fn spot_deploy_set_deployer_trading_fee_share(
+        &self,
+        token: u32,
+        share: String,
+    ) -> Result<ExchangeResponseStatus> {
+        let action =
+            SpotDeploy::SetDeployerTradingFeeShare(SetDeployerTradingFeeShare { token, share });
+        self.sign_and_post_l1_action(Actions::SpotDeploy(action))
+            .await
+    }
+
+    /// Registers a new asset on the perp DEX `dex`. `schema` is required when the asset is
+    /// the first of a new DEX.
+    pub async fn perp_deploy_register_asset(
+        &self,
+        dex: String,
+        max_gas: Option<u64>,
+        asset_request: PerpAssetRequest,
+        schema: Option<PerpDexSchema>,
+    ) -> Result<ExchangeResponseStatus> {
+        let schema = schema.map(|schema| PerpDexSchema {
+            oracle_updater: schema.oracle_updater.map(|updater| updater.to_lowercase()),
+            ..schema
+        });
+        let action = PerpDeploy::RegisterAsset(RegisterAsset {
+            max_gas,
+            asset_request,
+            dex,
+            schema,
+        });
+        self.sign_and_post_l1_action(Actions::PerpDeploy(action))
+            .await
+    }
+
+    /// Sets oracle prices of the perp DEX `dex`. `all_mark_pxs` holds one map per mark price
+    /// source; all prices are keyed by coin.
+    pub async fn perp_deploy_set_oracle(
+        &self,
+        dex: String,
+        oracle_pxs: BTreeMap<String, String>,
+        all_mark_pxs: Vec<BTreeMap<String, String>>,
+        external_perp_pxs: BTreeMap<String, String>,
+    ) -> Result<ExchangeResponseStatus> {
+        let action = PerpDeploy::SetOracle(SetOracle {
+            dex,
+            oracle_pxs: oracle_pxs.into_iter().collect(),
+            mark_pxs: all_mark_pxs
+                .into_iter()
+                .map(|mark_pxs| mark_pxs.into_iter().collect())
+                .collect(),
+            external_perp_pxs: external_perp_pxs.into_iter().collect(),
+        });
+        self.sign_and_post_l1_action(Actions::PerpDeploy(action))
+            .await
+    }
+
+    /// Moves `wei` of HYPE (8 decimals) from the spot balance into the staking balance.
+    pub async fn c_deposit(&self, wei: u64) -> Result<ExchangeResponseStatus> {
+        let nonce = self.nonce_manager.next_nonce();
+        let action = CDeposit {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            wei,
+            nonce,
         };
-        let req_json = serde_json::to_string(&req).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
-        self.http_client
-            .post(format!("{}/exchange/claimRewards", self.base_url))
-            .body(req_json)
-            .send()
+        self.sign_and_post_user_action(action, nonce).await
+    }
+
+    /// Moves `wei` of undelegated HYPE from the staking balance back to the spot balance.
+    pub async fn c_withdraw(&self, wei: u64) -> Result<ExchangeResponseStatus> {
+        let nonce = self.nonce_manager.next_nonce();
+        let action = CWithdraw {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            wei,
+            nonce,
+        };
+        self.sign_and_post_user_action(action, nonce).await
+    }
+
+    /// Delegates `wei` of staked HYPE to `validator`, or undelegates it when `is_undelegate`.
+    ///
+    /// Delegations are locked for a while after each delegation. Undelegating checks the
+    /// wallet's delegation to `validator` first and fails without signing while it is still
+    /// locked or smaller than `wei`.
+    pub async fn token_delegate(
+        &self,
+        validator: Address,
+        wei: u64,
+        is_undelegate: bool,
+    ) -> Result<ExchangeResponseStatus> {
+        if is_undelegate {
+            self.check_undelegation(validator, wei).await?;
+        }
+
+        let nonce = self.nonce_manager.next_nonce();
+        let action = TokenDelegate {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            validator: format!("{validator:#x}"),
+            wei,
+            is_undelegate,
+            nonce,
+        };
+        self.sign_and_post_user_action(action, nonce).await
+    }
+
+    /// Converts the wallet's account to a multi-sig user controlled by `signers`. Afterwards
+    /// every action of the account has to go through [`ExchangeClient::multi_sig`].
+    pub async fn convert_to_multi_sig_user(
+        &self,
+        signers: MultiSigSigners,
+    ) -> Result<ExchangeResponseStatus> {
+        let nonce = self.nonce_manager.next_nonce();
+        let action = ConvertToMultiSigUser {
+            signature_chain_id: self.signature_chain_id(),
+            hyperliquid_chain: self.hyperliquid_chain(),
+            signers: signers.to_action_string()?,
+            nonce,
+        };
+        self.sign_and_post_user_action(action, nonce).await
+    }
+
+    /// Starts a multi-sig transaction of `multi_sig_user` submitted by this client's wallet,
+    /// looking up the user's authorized signers. User-signed actions must carry `nonce`.
+    pub async fn multi_sig_transaction(
+        &self,
+        multi_sig_user: Address,
+        action: Actions,
+        nonce: u64,
+    ) -> Result<MultiSigTransaction> {
+        let info_client = InfoClient::new(self.base_url.clone());
+        let signers = info_client
+            .user_to_multi_sig_signers(multi_sig_user)
+            .await?
+            .ok_or_else(|| {
+                HyperliquidError::InvalidParameter(format!(
+                    "{multi_sig_user} is not a multi-sig user"
+                ))
+            })?;
+        MultiSigTransaction::new(
+            multi_sig_user,
+            self.wallet()?.address(),
+            signers,
+            action,
+            nonce,
+            None,
+            self.is_mainnet(),
+            self.signature_chain_id(),
+        )
+    }
+
+    /// Submits a multi-sig transaction that collected enough signatures, signed by the
+    /// client's wallet as the outer signer.
+    pub async fn multi_sig(
+        &self,
+        transaction: &MultiSigTransaction,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = self.wallet()?;
+        if wallet.address() != transaction.outer_signer() {
+            return Err(HyperliquidError::AuthenticationError(format!(
+                "transaction must be submitted by its outer signer {}",
+                transaction.outer_signer()
+            )));
+        }
+
+        let action = transaction.to_action()?;
+        let hash = action.signing_hash(
+            transaction.nonce(),
+            transaction.vault_address(),
+            transaction.is_mainnet(),
+        )?;
+        let signature = sign_l1_action(hash, wallet).await?;
+        self.post_action(
+            action,
+            signature.to_rsv(),
+            transaction.nonce(),
+            transaction.vault_address(),
+        )
+        .await
+    }
+
+    /// Exports `action` for signing elsewhere, on behalf of the client's vault for L1 actions.
+    /// User-signed actions keep their own nonce.
+    pub fn signing_envelope(&self, action: Actions) -> Result<SigningEnvelope> {
+        let nonce = action
+            .user_signed_nonce()
+            .unwrap_or_else(|| self.nonce_manager.next_nonce());
+        SigningEnvelope::new(action, nonce, self.vault_address, self.chain())
+    }
+
+    /// Submits an envelope signed elsewhere. Needs no wallet.
+    pub async fn broadcast(&self, envelope: &SigningEnvelope) -> Result<ExchangeResponseStatus> {
+        if envelope.chain() != self.chain() {
+            return Err(HyperliquidError::InvalidParameter(format!(
+                "envelope was signed for {:?}, client is on {:?}",
+                envelope.chain(),
+                self.chain()
+            )));
+        }
+        envelope.check()?;
+        let signature = envelope.signature().ok_or_else(|| {
+            HyperliquidError::SignatureError("envelope is not signed".to_string())
+        })?;
+
+        self.post_action(
+            envelope.action().clone(),
+            signature,
+            envelope.nonce(),
+            envelope.vault_address(),
+        )
+        .await
+    }
+
+    /// Makes the wallet's HyperEVM transactions go into large blocks, or back into small ones.
+    pub async fn evm_user_modify(&self, using_big_blocks: bool) -> Result<ExchangeResponseStatus> {
+        let action = Actions::EvmUserModify(EvmUserModify { using_big_blocks });
+        self.sign_and_post_l1_action_with_vault(action, None).await
+    }
+
+    /// Signs a noop with `nonce` on behalf of the client's vault, so that no other action
+    /// signed with it, e.g. a leaked pre-signed one, can be executed anymore.
+    pub async fn invalidate_nonce(&self, nonce: u64) -> Result<ExchangeResponseStatus> {
+        self.sign_and_post_l1_action_with_nonce(Actions::Noop, nonce, self.vault_address)
             .await
-            .map_err(HyperliquidError::from)?;
+    }
+
+    /// Invalidates every nonce the client's nonce manager handed out within `window` (in
+    /// milliseconds since the epoch), reporting which of them had already been consumed. A
+    /// nonce that fails to burn doesn't stop the others from being burned.
+    pub async fn burn_nonces(&self, window: RangeInclusive<u64>) -> Result<NonceBurnReport> {
+        let mut report = NonceBurnReport::default();
+        for nonce in self.nonce_manager.issued_between(window) {
+            match self.invalidate_nonce(nonce).await {
+                Ok(ExchangeResponseStatus::Ok(_)) => report.burned.push(nonce),
+                Ok(ExchangeResponseStatus::Err(e)) if is_duplicate_nonce_error(&e) => {
+                    report.consumed.push(nonce)
+                }
+                Ok(ExchangeResponseStatus::Err(e)) => report.failed.push((nonce, e)),
+                Err(e) => report.failed.push((nonce, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    async fn check_undelegation(&self, validator: Address, wei: u64) -> Result<()> {
+        let info_client = InfoClient::new(self.base_url.clone());
+        let delegations = info_client.delegations(self.wallet()?.address()).await?;
+        let delegation = delegations
+            .iter()
+            .find(|delegation| delegation.validator == validator)
+            .ok_or_else(|| {
+                HyperliquidError::PositionError(format!("no delegation to {validator}"))
+            })?;
+
+        let now = Utc::now().timestamp_millis() as u64;
+        if delegation.is_locked(now) {
+            return Err(HyperliquidError::PositionError(format!(
+                "delegation to {validator} is locked until {}",
+                delegation.locked_until_timestamp
+            )));
+        }
+        if delegation.amount_wei()? < wei {
+            return Err(HyperliquidError::InsufficientFunds(format!(
+                "delegation to {validator} is {}, cannot undelegate {wei} wei",
+                delegation.amount
+            )));
+        }
         Ok(())
     }
 }
 
+/// Whether the exchange rejected an action because its nonce was already used, as opposed to
+/// e.g. being outside the accepted nonce window.
+fn is_duplicate_nonce_error(error: &str) -> bool {
+    error.to_lowercase().contains("duplicate nonce")
+}
+
+/// Checks that `amount` is a positive decimal with at most `decimals` decimals.
+///
+/// Spot amounts are checked against the token's wei decimals only: balances are held in wei
+/// and transfers may move any wei amount, including dust below the order size step that
+/// `szDecimals` sets for trading.
+fn transfer_amount(amount: &str, decimals: u32) -> Result<String> {
+    match decimal_to_wei(amount, decimals) {
+        Some(wei) if wei > 0 => Ok(amount.to_string()),
+        _ => Err(HyperliquidError::InvalidParameter(format!(
+            "invalid amount {amount}, expected a positive decimal with at most {decimals} decimals"
+        ))),
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
-    use mockito::{Server, ServerGuard};
+    use crate::exchange::{ExchangeDataStatus, Order};
+    use mockito::{Matcher, Server, ServerGuard};
+    use serde_json::json;
 
-    fn setup_mock_server() -> (ExchangeClient, ServerGuard) {
-        let mut server = Server::new();
-        let client = ExchangeClient::new(server.url());
+    const TEST_PRIVATE_KEY: &str =
+        "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+
+    async fn setup_signing_client() -> (ExchangeClient, ServerGuard) {
+        let mut server = Server::new_async().await;
+        server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "meta"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"universe":[{"name":"BTC","szDecimals":5,"maxLeverage":40},{"name":"ETH","szDecimals":4,"maxLeverage":25}]}"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "spotMeta"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"tokens":[{"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},{"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true},{"name":"HFUN","szDecimals":2,"weiDecimals":8,"index":2,"tokenId":"0xbaf265ef389da684513d98d68edf4eae","isCanonical":false,"evmContract":{"address":"0xa320d9f65ec992eff38622c63627856382db726c","evm_extra_wei_decimals":10}}],"universe":[{"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true},{"tokens":[2,0],"name":"@1","index":1,"isCanonical":false}]}"#)
+            .create_async()
+            .await;
+        let wallet = TEST_PRIVATE_KEY.parse::<LocalWallet>().unwrap();
+        let client = ExchangeClient::new_with_wallet(server.url(), wallet, None)
+            .await
+            .unwrap();
         (client, server)
     }
 
     #[tokio::test]
-    async fn test_stake() {
-        let (client, mut server) = setup_mock_server();
-        let mock = server.mock("POST", "/exchange/stake")
+    async fn test_order_with_builder() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "order",
+                    "orders": [{
+                        "a": 1,
+                        "b": true,
+                        "p": "1800",
+                        "s": "0.1",
+                        "r": false,
+                        "t": {"limit": {"tif": "Gtc"}}
+                    }],
+                    "grouping": "na",
+                    "builder": {"b": "0x1ab189b7801140900c711e458212f9c76f8dac79", "f": 10}
+                },
+                "vaultAddress": null
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":77738308}}]}}}"#)
+            .create_async()
+            .await;
+
+        let order = Order::buy("ETH").size(0.1).limit(1800).build().unwrap();
+        let builder = BuilderInfo {
+            builder: "0x1ab189B7801140900C711E458212F9c76F8dAC79".to_string(),
+            fee: 10,
+        };
+        let response = client.order(order, Some(builder)).await.unwrap();
+        match response {
+            ExchangeResponseStatus::Ok(response) => {
+                let statuses = response.data.unwrap().statuses;
+                assert!(matches!(&statuses[0], ExchangeDataStatus::Resting(order) if order.oid == 77738308));
+            }
+            ExchangeResponseStatus::Err(e) => panic!("unexpected error {e}"),
+        }
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_spot_orders() {
+        let (client, mut server) = setup_signing_client().await;
+        let purr_mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "order", "orders": [{"a": 10000, "p": "0.00002378", "s": "1000000"}]}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":1}}]}}}"#)
+            .create_async()
+            .await;
+        let hfun_mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "order", "orders": [{"a": 10001, "p": "12.346", "s": "1.23"}]}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":2}}]}}}"#)
+            .create_async()
+            .await;
+
+        let order = Order::buy("PURR/USDC")
+            .size(1000000.4)
+            .limit(0.0000237812)
+            .build()
+            .unwrap();
+        client.order(order, None).await.unwrap();
+        let order = Order::sell("@1").size(1.234).limit(12.34567).build().unwrap();
+        client.order(order, None).await.unwrap();
+
+        purr_mock.assert_async().await;
+        hfun_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_order_size_rounds_to_zero() {
+        let (client, _server) = setup_signing_client().await;
+        let order = Order::buy("PURR/USDC").size(0.4).limit(0.2).build().unwrap();
+        assert!(matches!(
+            client.order(order, None).await,
+            Err(HyperliquidError::InvalidSize(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_spot_deploy_user_genesis() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "spotDeploy",
+                    "userGenesis": {
+                        "token": 1,
+                        "userAndWei": [["0x0d1d9635d0640821d15e323ac8adadfa9c111414", "100000000"]],
+                        "existingTokenAndWei": []
+                    }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let user = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+            .parse::<Address>()
+            .unwrap();
+        let response = client
+            .spot_deploy_user_genesis(1, vec![(user, "100000000".to_string())], vec![])
+            .await
+            .unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_perp_dex_order() {
+        let (mut client, mut server) = setup_signing_client().await;
+        server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "perpDexs"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[null,{"name":"abc","fullName":"abc dex","deployer":"0x5e89b26d8d66da9888c835c9bfcc2aa51813e152","oracleUpdater":null},{"name":"test","fullName":"test dex","deployer":"0x5e89b26d8d66da9888c835c9bfcc2aa51813e152","oracleUpdater":null}]"#)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "meta", "dex": "test"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"universe":[{"name":"test:ABC","szDecimals":2,"maxLeverage":10},{"name":"test:XYZ","szDecimals":1,"maxLeverage":10}]}"#)
+            .create_async()
+            .await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "order", "orders": [{"a": 120001, "p": "12.346", "s": "1.2"}]}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":3}}]}}}"#)
+            .create_async()
+            .await;
+
+        client.add_perp_dex("test").await.unwrap();
+        let order = Order::buy("test:XYZ").size(1.23).limit(12.34567).build().unwrap();
+        client.order(order, None).await.unwrap();
+        mock.assert_async().await;
+
+        assert!(client.add_perp_dex("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_order_unknown_asset() {
+        let (client, _server) = setup_signing_client().await;
+        let order = Order::buy("DOGE").size(10).limit(0.1).build().unwrap();
+        assert!(client.order(order, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_approve_builder_fee() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "approveBuilderFee",
+                    "signatureChainId": "0x66eee",
+                    "hyperliquidChain": "Testnet",
+                    "maxFeeRate": "0.01%",
+                    "builder": "0x1ab189b7801140900c711e458212f9c76f8dac79"
+                }
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body("{\"status\":\"success\"}")
-            .create();
-        
-        let result = client.stake(U256::from(1000000000000000000u64), Chain::Testnet).await;
-        assert!(result.is_ok());
-        mock.assert();
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let builder = "0x1ab189B7801140900C711E458212F9c76F8dAC79"
+            .parse::<Address>()
+            .unwrap();
+        let response = client
+            .approve_builder_fee(builder, "0.01%".to_string())
+            .await
+            .unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
     }
 
     #[tokio::test]
-    async fn test_start_unstake() {
-        let (client, mut server) = setup_mock_server();
-        let mock = server.mock("POST", "/exchange/startUnstake")
+    async fn test_c_deposit() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "cDeposit",
+                    "signatureChainId": "0x66eee",
+                    "hyperliquidChain": "Testnet",
+                    "wei": 100000000u64
+                }
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body("{\"status\":\"success\"}")
-            .create();
-        
-        let result = client.start_unstake(U256::from(1000000000000000000u64), Chain::Testnet).await;
-        assert!(result.is_ok());
-        mock.assert();
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let response = client.c_deposit(100_000_000).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
     }
 
     #[tokio::test]
-    async fn test_complete_unstake() {
-        let (client, mut server) = setup_mock_server();
-        let mock = server.mock("POST", "/exchange/completeUnstake")
+    async fn test_spot_send() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "spotSend",
+                    "signatureChainId": "0x66eee",
+                    "hyperliquidChain": "Testnet",
+                    "destination": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+                    "token": "PURR:0xc1fb593aeffbeb02f85e0308e9956a90",
+                    "amount": "12.5"
+                }
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body("{\"status\":\"success\"}")
-            .create();
-        
-        let result = client.complete_unstake(Chain::Testnet).await;
-        assert!(result.is_ok());
-        mock.assert();
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let destination: Address = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+            .parse()
+            .unwrap();
+        let status = client.spot_send(destination, "PURR", "12.5").await.unwrap();
+        assert_eq!(status, TransferStatus::Success);
+        let status = client
+            .spot_send(
+                destination,
+                "PURR:0xc1fb593aeffbeb02f85e0308e9956a90",
+                "12.5",
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, TransferStatus::Success);
+        mock.assert_async().await;
+
+        // PURR has 5 wei decimals
+        assert!(client
+            .spot_send(destination, "PURR", "0.000001")
+            .await
+            .is_err());
+        assert!(client
+            .spot_send(destination, "HFUN", "0.000000001")
+            .await
+            .is_err());
+        assert!(client
+            .spot_send(destination, "PURR:0x00", "1")
+            .await
+            .is_err());
+        assert!(client.spot_send(destination, "NOPE", "1").await.is_err());
     }
 
     #[tokio::test]
-    async fn test_claim_rewards() {
-        let (client, mut server) = setup_mock_server();
-        let mock = server.mock("POST", "/exchange/claimRewards")
+    async fn test_spot_send_below_size_decimals() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "spotSend",
+                    "token": "HFUN:0xbaf265ef389da684513d98d68edf4eae",
+                    "amount": "0.00012345"
+                }
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body("{\"status\":\"success\"}")
-            .create();
-        
-        let result = client.claim_rewards(Chain::Testnet).await;
-        assert!(result.is_ok());
-        mock.assert();
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        // HFUN trades in steps of 0.01 but moves in wei, down to 8 decimals
+        let status = client
+            .spot_send(Address::repeat_byte(1), "HFUN", "0.00012345")
+            .await
+            .unwrap();
+        assert_eq!(status, TransferStatus::Success);
+        mock.assert_async().await;
     }
 
     #[tokio::test]
-    async fn test_stake_error_response() {
-        let (client, mut server) = setup_mock_server();
-        let mock = server.mock("POST", "/exchange/stake")
-            .with_status(400)
+    async fn test_transfer_to_evm() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "spotSend",
+                    "destination": "0x2000000000000000000000000000000000000002",
+                    "token": "HFUN:0xbaf265ef389da684513d98d68edf4eae",
+                    "amount": "3.5"
+                }
+            })))
+            .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body("{\"error\":\"Invalid amount\"}")
-            .create();
-        
-        let result = client.stake(U256::from(0), Chain::Testnet).await;
-        assert!(result.is_err());
-        mock.assert();
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let status = client.transfer_to_evm("HFUN", "3.5").await.unwrap();
+        assert_eq!(status, TransferStatus::Success);
+        mock.assert_async().await;
+
+        // PURR is not linked to an EVM contract
+        assert!(client.transfer_to_evm("PURR", "1").await.is_err());
     }
 
     #[tokio::test]
-    async fn test_stake_request_validation() {
-        let (client, mut server) = setup_mock_server();
-        let mock = server.mock("POST", "/exchange/stake")
-            .match_body(mockito::Matcher::JsonString(r#"{"signatureChainId":"421614","hyperliquidChain":"arbitrum_testnet","amount":"1000000000000000000","time":"[0-9]+"}"#.to_string()))
+    async fn test_evm_user_modify() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "evmUserModify", "usingBigBlocks": true},
+                "vaultAddress": null
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body("{\"status\":\"success\"}")
-            .create();
-        
-        let result = client.stake(U256::from(1000000000000000000u64), Chain::Testnet).await;
-        assert!(result.is_ok());
-        mock.assert();
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let response = client.evm_user_modify(true).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_referral_actions() {
+        let (client, mut server) = setup_signing_client().await;
+        let register_mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "registerReferrer", "code": "MYCODE"},
+                "vaultAddress": null
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+        let state_mock = server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "referral"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"referredBy":null,"cumVlm":"0.0","unclaimedRewards":"0.0","claimedRewards":"0.0","builderRewards":"0.0","referrerState":{"stage":"needToCreateCode"},"rewardHistory":[]}"#)
+            .create_async()
+            .await;
+
+        let response = client.register_referrer("MYCODE".to_string()).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        register_mock.assert_async().await;
+
+        assert!(matches!(
+            client.claim_referral_rewards().await,
+            Err(HyperliquidError::InsufficientFunds(_))
+        ));
+        state_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_usd_send_rejected() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "usdSend", "amount": "1.5"}
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"err","response":"Insufficient balance for transfer"}"#)
+            .create_async()
+            .await;
+
+        let destination: Address = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+            .parse()
+            .unwrap();
+        let status = client.usd_send(destination, "1.5").await.unwrap();
+        assert_eq!(
+            status,
+            TransferStatus::Rejected("Insufficient balance for transfer".to_string())
+        );
+        mock.assert_async().await;
+
+        assert!(client.usd_send(destination, "0").await.is_err());
+        assert!(client.withdraw(destination, "1.0000001").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_burn_nonces() {
+        let (client, mut server) = setup_signing_client().await;
+        let nonces: Vec<u64> = (0..5)
+            .map(|_| client.nonce_manager().next_nonce())
+            .collect();
+        let responses = [
+            r#"{"status":"ok","response":{"type":"default"}}"#,
+            r#"{"status":"err","response":"Invalid nonce: duplicate nonce"}"#,
+            r#"{"status":"err","response":"User or API Wallet does not exist."}"#,
+            r#"{"status":"err","response":"Invalid nonce: nonce too old"}"#,
+            "<html>502 Bad Gateway</html>",
+        ];
+        let mut mocks = Vec::new();
+        for (nonce, body) in nonces.iter().zip(responses) {
+            let mock = server
+                .mock("POST", "/exchange")
+                .match_body(Matcher::PartialJson(json!({
+                    "action": {"type": "noop"},
+                    "nonce": nonce
+                })))
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+
+        let report = client.burn_nonces(nonces[0]..=nonces[4]).await.unwrap();
+        assert_eq!(report.burned, vec![nonces[0]]);
+        assert_eq!(report.consumed, vec![nonces[1]]);
+        assert_eq!(
+            report.failed[..2],
+            [
+                (nonces[2], "User or API Wallet does not exist.".to_string()),
+                (nonces[3], "Invalid nonce: nonce too old".to_string()),
+            ]
+        );
+        assert_eq!(report.failed.len(), 3);
+        assert_eq!(report.failed[2].0, nonces[4]);
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_delegate() {
+        let (client, mut server) = setup_signing_client().await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "tokenDelegate",
+                    "validator": "0x5ac99df645f3414876c816caa18b2d234024b487",
+                    "wei": 100000000u64,
+                    "isUndelegate": false
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let validator = "0x5ac99df645f3414876c816caa18b2d234024b487"
+            .parse::<Address>()
+            .unwrap();
+        let response = client
+            .token_delegate(validator, 100_000_000, false)
+            .await
+            .unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_undelegate_checks_lockup() {
+        let (client, mut server) = setup_signing_client().await;
+        let locked_until = Utc::now().timestamp_millis() + 86_400_000;
+        server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "delegations"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"[{{"validator":"0x5ac99df645f3414876c816caa18b2d234024b487","amount":"1.0","lockedUntilTimestamp":{locked_until}}},{{"validator":"0x000000000000000000000000000000000000dead","amount":"1.0","lockedUntilTimestamp":0}}]"#
+            ))
+            .create_async()
+            .await;
+        let exchange_mock = server
+            .mock("POST", "/exchange")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let locked = "0x5ac99df645f3414876c816caa18b2d234024b487"
+            .parse::<Address>()
+            .unwrap();
+        assert!(matches!(
+            client.token_delegate(locked, 100_000_000, true).await,
+            Err(HyperliquidError::PositionError(_))
+        ));
+        let unlocked = "0x000000000000000000000000000000000000dead"
+            .parse::<Address>()
+            .unwrap();
+        assert!(matches!(
+            client.token_delegate(unlocked, 200_000_000, true).await,
+            Err(HyperliquidError::InsufficientFunds(_))
+        ));
+        let unknown = Address::ZERO;
+        assert!(matches!(
+            client.token_delegate(unknown, 1, true).await,
+            Err(HyperliquidError::PositionError(_))
+        ));
+        exchange_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_vault_actions_ignore_client_vault() {
+        let (mut client, mut server) = setup_signing_client().await;
+        let vault = "0x1719884eB866cb12b2287399B15f7db5e7d775EA"
+            .parse::<Address>()
+            .unwrap();
+        client.vault_address = Some(vault);
+        let transfer_mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "vaultTransfer",
+                    "vaultAddress": "0x1719884eb866cb12b2287399b15f7db5e7d775ea",
+                    "isDeposit": true,
+                    "usd": 5000000
+                },
+                "vaultAddress": null
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+        let distribute_mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "vaultDistribute",
+                    "vaultAddress": "0x1719884eb866cb12b2287399b15f7db5e7d775ea",
+                    "usd": 1000000
+                },
+                "vaultAddress": null
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let response = client.vault_transfer(vault, true, 5_000_000).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        let response = client.vault_distribute(vault, 1_000_000).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        transfer_mock.assert_async().await;
+        distribute_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_multi_sig() {
+        let (client, mut server) = setup_signing_client().await;
+        let wallet = TEST_PRIVATE_KEY.parse::<LocalWallet>().unwrap();
+        let multi_sig_user = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+            .parse::<Address>()
+            .unwrap();
+        server
+            .mock("POST", "/info")
+            .match_body(Matcher::PartialJson(json!({"type": "userToMultiSigSigners"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"authorizedUsers":["{:#x}","0x0000000000000000000000000000000000000001"],"threshold":1}}"#,
+                wallet.address()
+            ))
+            .create_async()
+            .await;
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {
+                    "type": "multiSig",
+                    "signatureChainId": "0x66eee",
+                    "payload": {
+                        "multiSigUser": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+                        "outerSigner": format!("{:#x}", wallet.address()),
+                        "action": {"type": "updateLeverage", "asset": 1, "isCross": true, "leverage": 5}
+                    }
+                },
+                "nonce": 1700000000000u64,
+                "vaultAddress": null
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let action = Actions::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 5,
+        });
+        let mut transaction = client
+            .multi_sig_transaction(multi_sig_user, action, 1700000000000)
+            .await
+            .unwrap();
+        assert!(client.multi_sig(&transaction).await.is_err());
+        transaction.sign(&wallet).unwrap();
+        let response = client.multi_sig(&transaction).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed_envelope() {
+        let mut server = Server::new_async().await;
+        let client = ExchangeClient::new(server.url());
+        let action = Actions::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 5,
+        });
+        let mut envelope = client.signing_envelope(action).unwrap();
+        assert!(client.broadcast(&envelope).await.is_err());
+
+        let wallet = TEST_PRIVATE_KEY.parse::<LocalWallet>().unwrap();
+        envelope.sign(&wallet).unwrap();
+        let signature = envelope.signature().unwrap();
+        let mock = server
+            .mock("POST", "/exchange")
+            .match_body(Matcher::PartialJson(json!({
+                "action": {"type": "updateLeverage", "asset": 1, "isCross": true, "leverage": 5},
+                "signature": signature,
+                "nonce": envelope.nonce(),
+                "vaultAddress": null
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .create_async()
+            .await;
+
+        let response = client.broadcast(&envelope).await.unwrap();
+        assert!(matches!(response, ExchangeResponseStatus::Ok(_)));
+        mock.assert_async().await;
     }
 }
@@ -0,0 +1,567 @@
+use alloy_primitives::{Address, B256, U256};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use serde_json::value::RawValue;
+use std::fmt;
+
+use crate::{
+    constants::Chain,
+    errors::{HyperliquidError, Result},
+    exchange::actions::{
+        expiring_action_hash, Actions, HYPERLIQUID_EIP_PREFIX, SEND_MULTI_SIG_FIELDS,
+        USER_SIGNED_TYPES,
+    },
+    signature::{
+        create_signature::SignatureRsv, l1_action_signing_hash, recover_signer,
+        user_signed_action_hash,
+    },
+};
+
+/// A signed action as posted to `/exchange`.
+///
+/// Deserializing a payload received from elsewhere and calling
+/// [`ExchangePayload::recover_signer`] tells who signed it, e.g. to check that a pre-signed
+/// action comes from a known agent or master address before forwarding it. The action is kept
+/// as the JSON it was received as, so payloads signed by other SDKs verify whatever their key
+/// order, and actions the SDK doesn't model can be verified and forwarded unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangePayload {
+    pub action: Box<RawValue>,
+    pub signature: SignatureRsv,
+    pub nonce: u64,
+    #[serde(default)]
+    pub vault_address: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<u64>,
+}
+
+impl ExchangePayload {
+    pub fn new(
+        action: &Actions,
+        signature: SignatureRsv,
+        nonce: u64,
+        vault_address: Option<Address>,
+    ) -> Result<Self> {
+        Ok(Self {
+            action: serde_json::value::to_raw_value(action)?,
+            signature,
+            nonce,
+            vault_address,
+            expires_after: None,
+        })
+    }
+
+    /// The action as one of the SDK's [`Actions`]; fails for actions it doesn't model.
+    pub fn parse_action(&self) -> Result<Actions> {
+        Ok(serde_json::from_str(self.action.get())?)
+    }
+
+    /// Address that signed the payload for `chain`. For L1 actions this is the agent or
+    /// master key that signed, for user-signed actions the user, for multi-sig actions the
+    /// outer signer.
+    ///
+    /// The digest is recomputed from the action, so a payload whose action was altered after
+    /// signing recovers a different, unrelated address.
+    pub fn recover_signer(&self, chain: Chain) -> Result<Address> {
+        Ok(recover_signer(self.signing_hash(chain)?, &self.signature)?)
+    }
+
+    /// Fails unless the payload was signed by `expected` for `chain`.
+    pub fn verify_signer(&self, chain: Chain, expected: Address) -> Result<()> {
+        let signer = self.recover_signer(chain)?;
+        if signer != expected {
+            return Err(HyperliquidError::SignatureError(format!(
+                "payload signed by {signer}, expected {expected}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Addresses of the authorized users whose signatures a multi-sig payload carries, in
+    /// order; `None` for other actions.
+    pub fn recover_multi_sig_signers(&self, chain: Chain) -> Result<Option<Vec<Address>>> {
+        let action: OrderedJson = serde_json::from_str(self.action.get())?;
+        if action.action_type()? != "multiSig" {
+            return Ok(None);
+        }
+        let payload = action.field("payload")?;
+        let hash = multi_sig_signing_hash(
+            payload.field("action")?,
+            payload.field("multiSigUser")?.parse()?,
+            payload.field("outerSigner")?.parse()?,
+            self.nonce,
+            self.vault_address,
+            self.expires_after,
+            chain.is_mainnet(),
+        )?;
+        let signatures: Vec<SignatureRsv> = action.field("signatures")?.parse()?;
+        let signers = signatures
+            .iter()
+            .map(|signature| recover_signer(hash, signature))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(Some(signers))
+    }
+
+    /// Digest the signature of the payload commits to.
+    fn signing_hash(&self, chain: Chain) -> Result<B256> {
+        let action: OrderedJson = serde_json::from_str(self.action.get())?;
+        let action_type = action.action_type()?;
+        if action_type == "multiSig" {
+            // The outer signer signs the hash of the action without its type.
+            let OrderedJson::Object(mut fields) = action.clone() else {
+                unreachable!("actions with a type are objects")
+            };
+            fields.retain(|(key, _)| key != "type");
+            let multi_sig_action_hash = expiring_action_hash(
+                &OrderedJson::Object(fields),
+                self.nonce,
+                self.vault_address,
+                self.expires_after,
+            )?;
+            let message = serde_json::json!({
+                "hyperliquidChain": if chain.is_mainnet() { "Mainnet" } else { "Testnet" },
+                "multiSigActionHash": multi_sig_action_hash.to_string(),
+                "nonce": self.nonce,
+            });
+            return Ok(user_signed_action_hash(
+                &format!("{HYPERLIQUID_EIP_PREFIX}SendMultiSig"),
+                SEND_MULTI_SIG_FIELDS,
+                &message,
+                action.signature_chain_id()?,
+            )?);
+        }
+        match user_signed_fields(action_type) {
+            Some((primary_type, fields)) => Ok(user_signed_action_hash(
+                &format!("{HYPERLIQUID_EIP_PREFIX}{primary_type}"),
+                fields,
+                &action.signing_message()?,
+                action.signature_chain_id()?,
+            )?),
+            None => Ok(l1_action_signing_hash(
+                expiring_action_hash(&action, self.nonce, self.vault_address, self.expires_after)?,
+                chain.is_mainnet(),
+            )),
+        }
+    }
+}
+
+fn user_signed_fields(action_type: &str) -> Option<(&'static str, &'static [(&'static str, &'static str)])> {
+    USER_SIGNED_TYPES
+        .iter()
+        .find(|(tag, _, _)| *tag == action_type)
+        .map(|(_, primary_type, fields)| (*primary_type, *fields))
+}
+
+/// Digest the authorized users of `multi_sig_user` sign for the inner `action` of a multi-sig
+/// action submitted by `outer_signer`.
+fn multi_sig_signing_hash(
+    action: &OrderedJson,
+    multi_sig_user: Address,
+    outer_signer: Address,
+    nonce: u64,
+    vault_address: Option<Address>,
+    expires_after: Option<u64>,
+    is_mainnet: bool,
+) -> Result<B256> {
+    let multi_sig_user = format!("{multi_sig_user:#x}");
+    let outer_signer = format!("{outer_signer:#x}");
+
+    let Some((primary_type, fields)) = user_signed_fields(action.action_type()?) else {
+        let connection_id = expiring_action_hash(
+            &(multi_sig_user, outer_signer, action),
+            nonce,
+            vault_address,
+            expires_after,
+        )?;
+        return Ok(l1_action_signing_hash(connection_id, is_mainnet));
+    };
+
+    let mut message = action.signing_message()?;
+    message["payloadMultiSigUser"] = multi_sig_user.into();
+    message["outerSigner"] = outer_signer.into();
+    let mut multi_sig_fields = vec![
+        fields[0],
+        ("payloadMultiSigUser", "address"),
+        ("outerSigner", "address"),
+    ];
+    multi_sig_fields.extend_from_slice(&fields[1..]);
+    Ok(user_signed_action_hash(
+        &format!("{HYPERLIQUID_EIP_PREFIX}{primary_type}"),
+        &multi_sig_fields,
+        &message,
+        action.signature_chain_id()?,
+    )?)
+}
+
+/// A JSON value that keeps the key order of objects, which the msgpack encoding L1 actions
+/// are hashed in depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum OrderedJson {
+    Null,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    String(String),
+    Array(Vec<OrderedJson>),
+    Object(Vec<(String, OrderedJson)>),
+}
+
+impl OrderedJson {
+    fn field(&self, key: &str) -> Result<&OrderedJson> {
+        let missing = || HyperliquidError::InvalidParameter(format!("action has no {key}"));
+        match self {
+            OrderedJson::Object(fields) => fields
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value)
+                .ok_or_else(missing),
+            _ => Err(missing()),
+        }
+    }
+
+    fn parse<T: de::DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_value(serde_json::to_value(self)?)?)
+    }
+
+    fn action_type(&self) -> Result<&str> {
+        match self.field("type")? {
+            OrderedJson::String(action_type) => Ok(action_type),
+            action_type => Err(HyperliquidError::InvalidParameter(format!(
+                "invalid action type {action_type:?}"
+            ))),
+        }
+    }
+
+    fn signature_chain_id(&self) -> Result<U256> {
+        let chain_id: String = self.field("signatureChainId")?.parse()?;
+        chain_id.parse().map_err(|e| {
+            HyperliquidError::InvalidParameter(format!("invalid signature chain id {chain_id}: {e}"))
+        })
+    }
+
+    /// Typed data message of a user-signed action. Unnamed agents are signed with an empty
+    /// name but sent without one.
+    fn signing_message(&self) -> Result<serde_json::Value> {
+        let mut message = serde_json::to_value(self)?;
+        if self.action_type()? == "approveAgent" && message.get("agentName").is_none() {
+            message["agentName"] = "".into();
+        }
+        Ok(message)
+    }
+}
+
+impl Serialize for OrderedJson {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            OrderedJson::Null => serializer.serialize_unit(),
+            OrderedJson::Bool(value) => serializer.serialize_bool(*value),
+            OrderedJson::U64(value) => serializer.serialize_u64(*value),
+            OrderedJson::I64(value) => serializer.serialize_i64(*value),
+            OrderedJson::F64(value) => serializer.serialize_f64(*value),
+            OrderedJson::String(value) => serializer.serialize_str(value),
+            OrderedJson::Array(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            OrderedJson::Object(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (key, value) in fields {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderedJson {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct OrderedJsonVisitor;
+
+        impl<'de> Visitor<'de> for OrderedJsonVisitor {
+            type Value = OrderedJson;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON value")
+            }
+
+            fn visit_unit<E>(self) -> std::result::Result<OrderedJson, E> {
+                Ok(OrderedJson::Null)
+            }
+
+            fn visit_bool<E>(self, value: bool) -> std::result::Result<OrderedJson, E> {
+                Ok(OrderedJson::Bool(value))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> std::result::Result<OrderedJson, E> {
+                Ok(OrderedJson::U64(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> std::result::Result<OrderedJson, E> {
+                Ok(OrderedJson::I64(value))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> std::result::Result<OrderedJson, E> {
+                Ok(OrderedJson::F64(value))
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<OrderedJson, E> {
+                Ok(OrderedJson::String(value.to_string()))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<OrderedJson, A::Error> {
+                let mut values = Vec::new();
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(OrderedJson::Array(values))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<OrderedJson, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry()? {
+                    fields.push(field);
+                }
+                Ok(OrderedJson::Object(fields))
+            }
+        }
+
+        deserializer.deserialize_any(OrderedJsonVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::ARBITRUM_TESTNET_CHAIN_ID,
+        exchange::{
+            actions::CDeposit,
+            MultiSigSigners, MultiSigTransaction, UpdateLeverage,
+        },
+        HyperliquidSigner, LocalWallet,
+    };
+
+    fn wallet(key: &str) -> LocalWallet {
+        key.parse().unwrap()
+    }
+
+    fn signed_payload(
+        wallet: &LocalWallet,
+        action: Actions,
+        nonce: u64,
+        chain: Chain,
+    ) -> ExchangePayload {
+        let hash = action.signing_hash(nonce, None, chain.is_mainnet()).unwrap();
+        let signature = wallet.sign_hash(hash).unwrap().to_rsv();
+        let payload = ExchangePayload::new(&action, signature, nonce, None).unwrap();
+        // Round trip through the JSON posted to `/exchange`
+        serde_json::from_str(&serde_json::to_string(&payload).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_recover_l1_signer() {
+        let wallet = wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e");
+        let action = Actions::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 5,
+        });
+        let payload = signed_payload(&wallet, action, 1700000000000, Chain::Mainnet);
+        assert_eq!(payload.recover_signer(Chain::Mainnet).unwrap(), wallet.address());
+        payload
+            .verify_signer(Chain::Mainnet, wallet.address())
+            .unwrap();
+        assert!(payload
+            .verify_signer(Chain::Testnet, wallet.address())
+            .is_err());
+
+        let mut tampered = payload.clone();
+        tampered.nonce += 1;
+        assert_ne!(tampered.recover_signer(Chain::Mainnet).unwrap(), wallet.address());
+        assert_eq!(payload.recover_multi_sig_signers(Chain::Mainnet).unwrap(), None);
+    }
+
+    #[test]
+    fn test_recover_user_signed_signer() {
+        let wallet = wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e");
+        let action = Actions::CDeposit(CDeposit {
+            signature_chain_id: U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100_000_000,
+            nonce: 1700000000000,
+        });
+        let payload = signed_payload(&wallet, action, 1700000000000, Chain::Testnet);
+        payload
+            .verify_signer(Chain::Testnet, wallet.address())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_recover_multi_sig_signers() {
+        let outer = wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e");
+        let other = wallet("0123456789012345678901234567890123456789012345678901234567890123");
+        let signers = MultiSigSigners {
+            authorized_users: vec![outer.address(), other.address()],
+            threshold: 2,
+        };
+        let action = Actions::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: false,
+            leverage: 2,
+        });
+        let mut transaction = MultiSigTransaction::new(
+            Address::repeat_byte(7),
+            outer.address(),
+            signers,
+            action,
+            1700000000000,
+            None,
+            false,
+            U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+        )
+        .unwrap();
+        transaction.sign(&other).unwrap();
+        transaction.sign(&outer).unwrap();
+
+        let payload = signed_payload(
+            &outer,
+            transaction.to_action().unwrap(),
+            1700000000000,
+            Chain::Testnet,
+        );
+        payload
+            .verify_signer(Chain::Testnet, outer.address())
+            .unwrap();
+        assert_eq!(
+            payload.recover_multi_sig_signers(Chain::Testnet).unwrap(),
+            Some(vec![other.address(), outer.address()])
+        );
+    }
+
+    #[test]
+    fn test_verify_python_sdk_payloads() {
+        let wallet = wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e");
+
+        // Laid out as the Python SDK posts them: `expiresAfter` always present, transfers with
+        // their fields before the type and the chain fields appended last.
+        let order: ExchangePayload = serde_json::from_str(r#"{
+            "action": {"type": "order", "orders": [{"a": 1, "b": true, "p": "2000.0", "s": "3.5", "r": false, "t": {"limit": {"tif": "Ioc"}}}], "grouping": "na"},
+            "nonce": 1583838,
+            "signature": {"r": "0x77957e58e70f43b6b68581f2dc42011fc384538a2e5b7bf42d5b936f19fbb673", "s": "0x60721a8598727230f67080efee48c812a6a4442013fd3b0eed509171bef9f23f", "v": 28},
+            "vaultAddress": null,
+            "expiresAfter": null
+        }"#).unwrap();
+        order.verify_signer(Chain::Mainnet, wallet.address()).unwrap();
+        assert!(matches!(order.parse_action().unwrap(), Actions::BulkOrder(_)));
+
+        let usd_send: ExchangePayload = serde_json::from_str(r#"{
+            "action": {"destination": "0x0D1d9635D0640821d15e323ac8AdADfA9c111414", "amount": "1", "time": 1690393044548, "type": "usdSend", "signatureChainId": "0x66eee", "hyperliquidChain": "Testnet"},
+            "nonce": 1690393044548,
+            "signature": {"r": "0x214d507bbdaebba52fa60928f904a8b2df73673e3baba6133d66fe846c7ef704", "s": "0x51e82453a6d8db124e7ed6e60fa00d4b7c46e4d96cb2bd61fd81b6e8953cc9d2", "v": 27},
+            "vaultAddress": null,
+            "expiresAfter": null
+        }"#).unwrap();
+        usd_send
+            .verify_signer(Chain::Testnet, wallet.address())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_verify_actions_outside_the_sdk() {
+        #[derive(Serialize)]
+        struct CreateSubAccount {
+            r#type: &'static str,
+            name: &'static str,
+        }
+
+        let wallet = wallet("e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e");
+        let sign = |hash| serde_json::to_string(&wallet.sign_hash(hash).unwrap().to_rsv()).unwrap();
+
+        let action = CreateSubAccount { r#type: "createSubAccount", name: "sub" };
+        let hash = expiring_action_hash(&action, 1700000000000, None, Some(1700000060000)).unwrap();
+        let json = format!(
+            r#"{{"action":{{"type":"createSubAccount","name":"sub"}},"nonce":1700000000000,"signature":{},"vaultAddress":null,"expiresAfter":1700000060000}}"#,
+            sign(l1_action_signing_hash(hash, false))
+        );
+        let payload: ExchangePayload = serde_json::from_str(&json).unwrap();
+        payload
+            .verify_signer(Chain::Testnet, wallet.address())
+            .unwrap();
+        assert!(payload.parse_action().is_err());
+        // Forwarded unchanged
+        assert_eq!(payload.action.get(), r#"{"type":"createSubAccount","name":"sub"}"#);
+        let mut expired = payload.clone();
+        expired.expires_after = None;
+        assert!(expired.verify_signer(Chain::Testnet, wallet.address()).is_err());
+
+        // An unnamed agent is signed with an empty name the payload doesn't carry.
+        let (_, fields) = user_signed_fields("approveAgent").unwrap();
+        let hash = user_signed_action_hash(
+            "HyperliquidTransaction:ApproveAgent",
+            fields,
+            &serde_json::json!({
+                "hyperliquidChain": "Testnet",
+                "agentAddress": "0x1234567890123456789012345678901234567890",
+                "agentName": "",
+                "nonce": 1700000000000u64,
+            }),
+            U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+        )
+        .unwrap();
+        let json = format!(
+            r#"{{"action":{{"type":"approveAgent","agentAddress":"0x1234567890123456789012345678901234567890","nonce":1700000000000,"signatureChainId":"0x66eee","hyperliquidChain":"Testnet"}},"nonce":1700000000000,"signature":{},"vaultAddress":null}}"#,
+            sign(hash)
+        );
+        let payload: ExchangePayload = serde_json::from_str(&json).unwrap();
+        payload
+            .verify_signer(Chain::Testnet, wallet.address())
+            .unwrap();
+
+        let send_asset = serde_json::json!({
+            "type": "sendAsset",
+            "signatureChainId": "0x66eee",
+            "hyperliquidChain": "Testnet",
+            "destination": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+            "sourceDex": "",
+            "destinationDex": "spot",
+            "token": "USDC:0x6d1e7cde53ba9467b783cb7c530ce054",
+            "amount": "1",
+            "fromSubAccount": "",
+            "nonce": 1700000000000u64,
+        });
+        let (_, _, fields) = USER_SIGNED_TYPES
+            .iter()
+            .find(|(tag, _, _)| *tag == "sendAsset")
+            .unwrap();
+        let hash = user_signed_action_hash(
+            "HyperliquidTransaction:SendAsset",
+            fields,
+            &send_asset,
+            U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+        )
+        .unwrap();
+        let json = format!(
+            r#"{{"action":{send_asset},"nonce":1700000000000,"signature":{},"vaultAddress":null}}"#,
+            sign(hash)
+        );
+        let payload: ExchangePayload = serde_json::from_str(&json).unwrap();
+        payload
+            .verify_signer(Chain::Testnet, wallet.address())
+            .unwrap();
+    }
+}
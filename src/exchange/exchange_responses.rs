@@ -43,3 +43,31 @@ pub enum ExchangeResponseStatus {
     Ok(ExchangeResponse),
     Err(String),
 }
+
+/// Outcome of burning a batch of nonces with noop actions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NonceBurnReport {
+    /// Nonces that were still unused and are now invalidated.
+    pub burned: Vec<u64>,
+    /// Nonces the exchange had already consumed, i.e. an action signed with them went through.
+    pub consumed: Vec<u64>,
+    /// Nonces whose noop was rejected for any other reason, e.g. a nonce outside the accepted
+    /// time window, or could not be sent, with the error.
+    pub failed: Vec<(u64, String)>,
+}
+
+/// Outcome of a transfer or withdrawal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferStatus {
+    Success,
+    Rejected(String),
+}
+
+impl From<ExchangeResponseStatus> for TransferStatus {
+    fn from(status: ExchangeResponseStatus) -> Self {
+        match status {
+            ExchangeResponseStatus::Ok(_) => TransferStatus::Success,
+            ExchangeResponseStatus::Err(e) => TransferStatus::Rejected(e),
+        }
+    }
+}
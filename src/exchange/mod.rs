@@ -2,21 +2,29 @@ pub mod actions;
 mod builder;
 mod cancel;
 mod exchange_client;
+mod exchange_payload;
 mod exchange_responses;
 mod modify;
+mod multi_sig;
 mod order;
+mod order_builder;
+mod perp_deploy;
+mod signing_envelope;
+mod spot_deploy;
 
 pub use actions::{
-    ApproveBuilderFee, BulkCancel, BulkCancelCloid, BulkModify, BulkOrder, SetReferrer,
-    UpdateLeverage, VaultTransfer,
+    Actions, ApproveBuilderFee, BulkCancel, BulkCancelCloid, BulkModify, BulkOrder, CDeposit,
+    CWithdraw, ConvertToMultiSigUser, CreateVault, EvmUserModify, MultiSig, MultiSigPayload,
+    RegisterReferrer, SetReferrer, SpotSend, TokenDelegate, UpdateIsolatedMargin,
+    UpdateLeverage, UsdSend, VaultDistribute, VaultModify, VaultTransfer, Withdraw3,
 };
 pub use builder::*;
 pub use cancel::{ClientCancelRequest, ClientCancelRequestCloid};
-pub use exchange_client::{
-    ExchangeClient,
-    ExchangeResponse,
-    ExchangeResponseStatus,
-    ExchangeDataStatus,
+pub use exchange_client::ExchangeClient;
+pub use exchange_payload::ExchangePayload;
+pub use exchange_responses::{
+    ExchangeDataStatus, ExchangeDataStatuses, ExchangeResponse, ExchangeResponseStatus,
+    FilledOrder, NonceBurnReport, RestingOrder, TransferStatus,
 };
 pub use exchange_responses::{
     ExchangeResponse as ExchangeResponseType,
@@ -24,7 +32,15 @@ pub use exchange_responses::{
     ExchangeDataStatus as ExchangeDataStatusType,
 };
 pub use modify::{ClientModifyRequest, ModifyRequest};
+pub use multi_sig::{MultiSigSigners, MultiSigTransaction, PartialSignature};
 pub use order::{
     ClientLimit, ClientOrder, ClientOrderRequest, ClientTrigger, MarketCloseParams,
     MarketOrderParams, Order,
 };
+pub use order_builder::OrderBuilder;
+pub use perp_deploy::{PerpAssetRequest, PerpDeploy, PerpDexSchema, RegisterAsset, SetOracle};
+pub use signing_envelope::SigningEnvelope;
+pub use spot_deploy::{
+    Genesis, RegisterHyperliquidity, RegisterSpot, RegisterToken, SetDeployerTradingFeeShare,
+    SpotDeploy, TokenSpec, UserGenesis,
+};
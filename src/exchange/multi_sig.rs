@@ -0,0 +1,504 @@
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{HyperliquidError, Result},
+    exchange::actions::{Actions, MultiSig, MultiSigPayload},
+    signature::{create_signature::SignatureRsv, recover_signer, HyperliquidSigner},
+};
+
+/// Authorized users of a multi-sig user and how many of them must sign each action.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigSigners {
+    pub authorized_users: Vec<Address>,
+    pub threshold: usize,
+}
+
+impl MultiSigSigners {
+    /// The `signers` field of a `convertToMultiSigUser` action, with lowercase sorted users.
+    pub(crate) fn to_action_string(&self) -> Result<String> {
+        if self.threshold == 0 || self.threshold > self.authorized_users.len() {
+            return Err(HyperliquidError::InvalidParameter(format!(
+                "threshold {} must be between 1 and the {} authorized users",
+                self.threshold,
+                self.authorized_users.len()
+            )));
+        }
+        let mut authorized_users: Vec<String> = self
+            .authorized_users
+            .iter()
+            .map(|user| format!("{user:#x}"))
+            .collect();
+        authorized_users.sort();
+        authorized_users.dedup();
+        Ok(serde_json::json!({
+            "authorizedUsers": authorized_users,
+            "threshold": self.threshold,
+        })
+        .to_string())
+    }
+
+    fn is_authorized(&self, user: Address) -> bool {
+        self.authorized_users.contains(&user)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PartialSignature {
+    pub signer: Address,
+    pub signature: SignatureRsv,
+}
+
+/// An action of a multi-sig user collecting the signatures of its authorized users.
+///
+/// The transaction serializes to JSON, so it can be handed from signer to signer, possibly
+/// offline. Each signer adds its signature with [`MultiSigTransaction::sign`], partial copies
+/// are combined with [`MultiSigTransaction::merge`], and the outer signer submits it with
+/// [`ExchangeClient::multi_sig`](crate::ExchangeClient::multi_sig) once it
+/// [`is_ready`](MultiSigTransaction::is_ready).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigTransaction {
+    multi_sig_user: Address,
+    outer_signer: Address,
+    signers: MultiSigSigners,
+    action: Actions,
+    nonce: u64,
+    vault_address: Option<Address>,
+    is_mainnet: bool,
+    signature_chain_id: U256,
+    signatures: Vec<PartialSignature>,
+}
+
+impl MultiSigTransaction {
+    /// Wraps `action` of `multi_sig_user`, to be submitted by `outer_signer` as a `multiSig`
+    /// action signed for `signature_chain_id`. User-signed actions must carry `nonce` as their
+    /// own nonce.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        multi_sig_user: Address,
+        outer_signer: Address,
+        signers: MultiSigSigners,
+        action: Actions,
+        nonce: u64,
+        vault_address: Option<Address>,
+        is_mainnet: bool,
+        signature_chain_id: U256,
+    ) -> Result<Self> {
+        if !signers.is_authorized(outer_signer) {
+            return Err(HyperliquidError::AuthenticationError(format!(
+                "outer signer {outer_signer} is not an authorized user of {multi_sig_user}"
+            )));
+        }
+        if matches!(action, Actions::MultiSig(_)) {
+            return Err(HyperliquidError::InvalidParameter(
+                "multi-sig actions can not be nested".to_string(),
+            ));
+        }
+        if let Some(action_nonce) = action.user_signed_nonce() {
+            if action_nonce != nonce {
+                return Err(HyperliquidError::InvalidParameter(format!(
+                    "action nonce {action_nonce} does not match multi-sig nonce {nonce}"
+                )));
+            }
+        }
+
+        Ok(Self {
+            multi_sig_user,
+            outer_signer,
+            signers,
+            action,
+            nonce,
+            vault_address,
+            is_mainnet,
+            signature_chain_id,
+            signatures: Vec::new(),
+        })
+    }
+
+    pub fn multi_sig_user(&self) -> Address {
+        self.multi_sig_user
+    }
+
+    pub fn outer_signer(&self) -> Address {
+        self.outer_signer
+    }
+
+    pub fn action(&self) -> &Actions {
+        &self.action
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn vault_address(&self) -> Option<Address> {
+        self.vault_address
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        self.is_mainnet
+    }
+
+    pub fn signature_chain_id(&self) -> U256 {
+        self.signature_chain_id
+    }
+
+    pub fn signatures(&self) -> &[PartialSignature] {
+        &self.signatures
+    }
+
+    /// Hash every authorized user signs: the inner action bound to the multi-sig user and
+    /// outer signer.
+    pub fn signing_hash(&self) -> Result<B256> {
+        Ok(self.action.multi_sig_signing_hash(
+            self.multi_sig_user,
+            self.outer_signer,
+            self.nonce,
+            self.vault_address,
+            self.is_mainnet,
+        )?)
+    }
+
+    /// Adds `signer`'s signature, replacing any it added before.
+    pub fn sign(&mut self, signer: &impl HyperliquidSigner) -> Result<()> {
+        let address = signer.address();
+        if !self.signers.is_authorized(address) {
+            return Err(HyperliquidError::AuthenticationError(format!(
+                "{address} is not an authorized user of {}",
+                self.multi_sig_user
+            )));
+        }
+        let signature = signer.sign_hash(self.signing_hash()?)?.to_rsv();
+        self.signatures.retain(|partial| partial.signer != address);
+        self.signatures.push(PartialSignature {
+            signer: address,
+            signature,
+        });
+        Ok(())
+    }
+
+    /// Adds the signatures collected on another copy of the same transaction, which must all
+    /// be valid signatures of authorized users.
+    pub fn merge(&mut self, other: MultiSigTransaction) -> Result<()> {
+        let hash = self.signing_hash()?;
+        if other.outer_signer != self.outer_signer
+            || other.signers != self.signers
+            || other.signature_chain_id != self.signature_chain_id
+            || other.signing_hash()? != hash
+        {
+            return Err(HyperliquidError::InvalidParameter(
+                "can not merge signatures of a different multi-sig transaction".to_string(),
+            ));
+        }
+        if let Some(partial) = other
+            .signatures
+            .iter()
+            .find(|partial| !self.is_valid(hash, partial))
+        {
+            return Err(HyperliquidError::SignatureError(format!(
+                "signature of {} does not recover to an authorized user",
+                partial.signer
+            )));
+        }
+        for partial in other.signatures {
+            if !self
+                .signatures
+                .iter()
+                .any(|existing| existing.signer == partial.signer)
+            {
+                self.signatures.push(partial);
+            }
+        }
+        Ok(())
+    }
+
+    /// Authorized users whose signature recovers against the
+    /// [`signing_hash`](MultiSigTransaction::signing_hash).
+    pub fn valid_signers(&self) -> Result<Vec<Address>> {
+        let hash = self.signing_hash()?;
+        Ok(self
+            .signatures
+            .iter()
+            .filter(|partial| self.is_valid(hash, partial))
+            .map(|partial| partial.signer)
+            .collect())
+    }
+
+    /// Whether enough authorized users validly signed to submit the transaction.
+    pub fn is_ready(&self) -> bool {
+        self.valid_signers()
+            .is_ok_and(|signers| signers.len() >= self.signers.threshold)
+    }
+
+    fn is_valid(&self, hash: B256, partial: &PartialSignature) -> bool {
+        self.signers.is_authorized(partial.signer)
+            && recover_signer(hash, &partial.signature)
+                .is_ok_and(|signer| signer == partial.signer)
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// The `multiSig` action wrapping the valid signatures collected.
+    pub(crate) fn to_action(&self) -> Result<Actions> {
+        let hash = self.signing_hash()?;
+        let signatures: Vec<SignatureRsv> = self
+            .signatures
+            .iter()
+            .filter(|partial| self.is_valid(hash, partial))
+            .map(|partial| partial.signature)
+            .collect();
+        if signatures.len() < self.signers.threshold {
+            return Err(HyperliquidError::SignatureError(format!(
+                "{} of {} required signatures collected",
+                signatures.len(),
+                self.signers.threshold
+            )));
+        }
+
+        Ok(Actions::MultiSig(MultiSig {
+            signature_chain_id: self.signature_chain_id,
+            signatures,
+            payload: MultiSigPayload {
+                multi_sig_user: format!("{:#x}", self.multi_sig_user),
+                outer_signer: format!("{:#x}", self.outer_signer),
+                action: Box::new(self.action.clone()),
+            },
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::{ARBITRUM_CHAIN_ID, ARBITRUM_TESTNET_CHAIN_ID},
+        exchange::actions::CDeposit,
+        signature::l1_action_signing_hash,
+        LocalWallet,
+    };
+
+    const SIGNER_KEYS: [&str; 3] = [
+        "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e",
+        "0123456789012345678901234567890123456789012345678901234567890123",
+        "1111111111111111111111111111111111111111111111111111111111111111",
+    ];
+
+    fn wallets() -> Vec<LocalWallet> {
+        SIGNER_KEYS
+            .iter()
+            .map(|key| key.parse::<LocalWallet>().unwrap())
+            .collect()
+    }
+
+    fn transaction(wallets: &[LocalWallet], action: Actions) -> MultiSigTransaction {
+        let signers = MultiSigSigners {
+            authorized_users: wallets[..2].iter().map(|wallet| wallet.address()).collect(),
+            threshold: 2,
+        };
+        let multi_sig_user = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+            .parse::<Address>()
+            .unwrap();
+        MultiSigTransaction::new(
+            multi_sig_user,
+            wallets[0].address(),
+            signers,
+            action,
+            1700000000000,
+            None,
+            false,
+            U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+        )
+        .unwrap()
+    }
+
+    fn c_deposit(nonce: u64) -> Actions {
+        Actions::CDeposit(CDeposit {
+            signature_chain_id: U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100_000_000,
+            nonce,
+        })
+    }
+
+    #[test]
+    fn test_collect_signatures_across_parties() {
+        let wallets = wallets();
+        let mut transaction = transaction(&wallets, c_deposit(1700000000000));
+        transaction.sign(&wallets[0]).unwrap();
+        assert!(!transaction.is_ready());
+
+        let mut remote = MultiSigTransaction::from_json(&transaction.to_json().unwrap()).unwrap();
+        remote.sign(&wallets[1]).unwrap();
+        transaction.merge(remote).unwrap();
+        assert!(transaction.is_ready());
+
+        let hash = transaction.signing_hash().unwrap();
+        for partial in transaction.signatures() {
+            assert_eq!(
+                recover_signer(hash, &partial.signature).unwrap(),
+                partial.signer
+            );
+        }
+
+        assert!(matches!(
+            transaction.sign(&wallets[2]),
+            Err(HyperliquidError::AuthenticationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_signatures_bind_multi_sig_user() {
+        let wallets = wallets();
+        let action = Actions::UpdateLeverage(crate::exchange::UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 5,
+        });
+        let transaction = transaction(&wallets, action.clone());
+        let l1_hash = l1_action_signing_hash(action.hash(1700000000000, None).unwrap(), false);
+        assert_ne!(transaction.signing_hash().unwrap(), l1_hash);
+
+        let other_user = MultiSigTransaction {
+            multi_sig_user: Address::ZERO,
+            ..transaction.clone()
+        };
+        assert_ne!(
+            transaction.signing_hash().unwrap(),
+            other_user.signing_hash().unwrap()
+        );
+        let mut transaction = transaction;
+        assert!(transaction.merge(other_user).is_err());
+    }
+
+    #[test]
+    fn test_multi_sig_action() {
+        let wallets = wallets();
+        let mut transaction = transaction(&wallets, c_deposit(1700000000000));
+        assert!(transaction.to_action().is_err());
+        transaction.sign(&wallets[1]).unwrap();
+        transaction.sign(&wallets[0]).unwrap();
+
+        let action = serde_json::to_value(transaction.to_action().unwrap()).unwrap();
+        assert_eq!(action["type"], "multiSig");
+        assert_eq!(action["signatureChainId"], "0x66eee");
+        assert_eq!(action["signatures"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            action["payload"]["multiSigUser"],
+            "0x0d1d9635d0640821d15e323ac8adadfa9c111414"
+        );
+        assert_eq!(
+            action["payload"]["outerSigner"],
+            format!("{:#x}", wallets[0].address())
+        );
+        assert_eq!(action["payload"]["action"]["type"], "cDeposit");
+
+        let mainnet = MultiSigTransaction {
+            signature_chain_id: U256::from(ARBITRUM_CHAIN_ID),
+            ..transaction
+        };
+        let action = serde_json::to_value(mainnet.to_action().unwrap()).unwrap();
+        assert_eq!(action["signatureChainId"], "0xa4b1");
+    }
+
+    #[test]
+    fn test_only_valid_signatures_count() {
+        let wallets = wallets();
+        let mut transaction = transaction(&wallets, c_deposit(1700000000000));
+        transaction.sign(&wallets[0]).unwrap();
+
+        // Signed by an unauthorized key but claimed by an authorized user.
+        let mut forged = transaction.clone();
+        forged.signatures.push(PartialSignature {
+            signer: wallets[1].address(),
+            signature: wallets[2]
+                .sign_hash(transaction.signing_hash().unwrap())
+                .unwrap()
+                .to_rsv(),
+        });
+        assert!(!forged.is_ready());
+        assert_eq!(forged.valid_signers().unwrap(), vec![wallets[0].address()]);
+        assert!(forged.to_action().is_err());
+        assert!(matches!(
+            transaction.merge(forged),
+            Err(HyperliquidError::SignatureError(_))
+        ));
+        assert_eq!(transaction.signatures().len(), 1);
+
+        // Signed by an authorized user for another transaction.
+        let mut other = transaction.clone();
+        other.signatures = vec![PartialSignature {
+            signer: wallets[1].address(),
+            signature: wallets[1]
+                .sign_hash(B256::repeat_byte(1))
+                .unwrap()
+                .to_rsv(),
+        }];
+        assert!(transaction.merge(other).is_err());
+        assert!(!transaction.is_ready());
+    }
+
+    #[test]
+    fn test_invalid_transactions() {
+        let wallets = wallets();
+        let signers = MultiSigSigners {
+            authorized_users: vec![wallets[0].address()],
+            threshold: 1,
+        };
+        assert!(MultiSigTransaction::new(
+            Address::ZERO,
+            wallets[1].address(),
+            signers.clone(),
+            c_deposit(1),
+            1,
+            None,
+            false,
+            U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+        )
+        .is_err());
+        assert!(MultiSigTransaction::new(
+            Address::ZERO,
+            wallets[0].address(),
+            signers.clone(),
+            c_deposit(1),
+            2,
+            None,
+            false,
+            U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+        )
+        .is_err());
+
+        let signers = MultiSigSigners {
+            authorized_users: vec![wallets[1].address(), wallets[0].address()],
+            threshold: 2,
+        };
+        let users: Vec<String> = {
+            let mut users: Vec<String> = wallets[..2]
+                .iter()
+                .map(|wallet| format!("{:#x}", wallet.address()))
+                .collect();
+            users.sort();
+            users
+        };
+        assert_eq!(
+            signers.to_action_string().unwrap(),
+            format!(
+                r#"{{"authorizedUsers":["{}","{}"],"threshold":2}}"#,
+                users[0], users[1]
+            )
+        );
+        assert!(MultiSigSigners {
+            threshold: 3,
+            ..signers
+        }
+        .to_action_string()
+        .is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use uuid::Uuid;
+
+use crate::errors::{HyperliquidError, Result};
+
+use super::order::{ClientLimit, ClientOrder, ClientOrderRequest, ClientTrigger, Order};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TriggerKind {
+    StopMarket,
+    StopLimit,
+    TakeProfitMarket,
+    TakeProfitLimit,
+}
+
+impl TriggerKind {
+    fn is_market(self) -> bool {
+        matches!(self, TriggerKind::StopMarket | TriggerKind::TakeProfitMarket)
+    }
+
+    fn tpsl(self) -> &'static str {
+        match self {
+            TriggerKind::StopMarket | TriggerKind::StopLimit => "sl",
+            TriggerKind::TakeProfitMarket | TriggerKind::TakeProfitLimit => "tp",
+        }
+    }
+}
+
+/// Fluent builder for [`ClientOrderRequest`]s, started with [`Order::buy`] or [`Order::sell`].
+///
+/// Limit orders default to `Gtc`. Trigger orders take no time in force, and market trigger
+/// orders use the trigger price as their limit price unless [`OrderBuilder::limit`] is set.
+#[derive(Debug, Clone)]
+pub struct OrderBuilder {
+    asset: String,
+    is_buy: bool,
+    sz: Option<f64>,
+    limit_px: Option<f64>,
+    tif: Option<&'static str>,
+    trigger: Option<(TriggerKind, f64)>,
+    reduce_only: bool,
+    cloid: Option<Uuid>,
+}
+
+impl Order {
+    pub fn buy(asset: impl Into<String>) -> OrderBuilder {
+        OrderBuilder::new(asset.into(), true)
+    }
+
+    pub fn sell(asset: impl Into<String>) -> OrderBuilder {
+        OrderBuilder::new(asset.into(), false)
+    }
+}
+
+impl OrderBuilder {
+    fn new(asset: String, is_buy: bool) -> Self {
+        Self {
+            asset,
+            is_buy,
+            sz: None,
+            limit_px: None,
+            tif: None,
+            trigger: None,
+            reduce_only: false,
+            cloid: None,
+        }
+    }
+
+    pub fn size(mut self, sz: impl Into<f64>) -> Self {
+        self.sz = Some(sz.into());
+        self
+    }
+
+    pub fn limit(mut self, px: impl Into<f64>) -> Self {
+        self.limit_px = Some(px.into());
+        self
+    }
+
+    pub fn gtc(mut self) -> Self {
+        self.tif = Some("Gtc");
+        self
+    }
+
+    pub fn ioc(mut self) -> Self {
+        self.tif = Some("Ioc");
+        self
+    }
+
+    pub fn post_only(mut self) -> Self {
+        self.tif = Some("Alo");
+        self
+    }
+
+    pub fn reduce_only(mut self) -> Self {
+        self.reduce_only = true;
+        self
+    }
+
+    pub fn cloid(mut self, cloid: Uuid) -> Self {
+        self.cloid = Some(cloid);
+        self
+    }
+
+    pub fn stop_market(mut self, trigger_px: impl Into<f64>) -> Self {
+        self.trigger = Some((TriggerKind::StopMarket, trigger_px.into()));
+        self
+    }
+
+    /// Stop order that rests at the price given to [`OrderBuilder::limit`] once triggered.
+    pub fn stop_limit(mut self, trigger_px: impl Into<f64>) -> Self {
+        self.trigger = Some((TriggerKind::StopLimit, trigger_px.into()));
+        self
+    }
+
+    pub fn take_profit_market(mut self, trigger_px: impl Into<f64>) -> Self {
+        self.trigger = Some((TriggerKind::TakeProfitMarket, trigger_px.into()));
+        self
+    }
+
+    /// Take profit order that rests at the price given to [`OrderBuilder::limit`] once triggered.
+    pub fn take_profit_limit(mut self, trigger_px: impl Into<f64>) -> Self {
+        self.trigger = Some((TriggerKind::TakeProfitLimit, trigger_px.into()));
+        self
+    }
+
+    pub fn build(self) -> Result<ClientOrderRequest> {
+        if self.asset.is_empty() {
+            return Err(HyperliquidError::InvalidAsset("asset is empty".to_string()));
+        }
+
+        let sz = self
+            .sz
+            .ok_or_else(|| HyperliquidError::InvalidSize("size is required".to_string()))?;
+        if !sz.is_finite() || sz <= 0.0 {
+            return Err(HyperliquidError::InvalidSize(format!(
+                "size must be positive, got {sz}"
+            )));
+        }
+
+        let (limit_px, order_type) = match self.trigger {
+            Some((kind, trigger_px)) => {
+                if let Some(tif) = self.tif {
+                    return Err(HyperliquidError::OrderError(format!(
+                        "trigger orders do not take a time in force, got {tif}"
+                    )));
+                }
+                check_price("trigger price", trigger_px)?;
+
+                let limit_px = match self.limit_px {
+                    Some(limit_px) => limit_px,
+                    None if kind.is_market() => trigger_px,
+                    None => {
+                        return Err(HyperliquidError::InvalidPrice(
+                            "limit price is required for stop and take profit limit orders"
+                                .to_string(),
+                        ))
+                    }
+                };
+
+                let order_type = ClientOrder::Trigger(ClientTrigger {
+                    is_market: kind.is_market(),
+                    trigger_px,
+                    tpsl: kind.tpsl().to_string(),
+                });
+                (limit_px, order_type)
+            }
+            None => {
+                let limit_px = self.limit_px.ok_or_else(|| {
+                    HyperliquidError::InvalidPrice("limit price is required".to_string())
+                })?;
+                let order_type = ClientOrder::Limit(ClientLimit {
+                    tif: self.tif.unwrap_or("Gtc").to_string(),
+                });
+                (limit_px, order_type)
+            }
+        };
+        check_price("limit price", limit_px)?;
+
+        Ok(ClientOrderRequest {
+            asset: self.asset,
+            is_buy: self.is_buy,
+            reduce_only: self.reduce_only,
+            limit_px,
+            sz,
+            cloid: self.cloid,
+            order_type,
+        })
+    }
+}
+
+fn check_price(name: &str, px: f64) -> Result<()> {
+    if !px.is_finite() || px <= 0.0 {
+        return Err(HyperliquidError::InvalidPrice(format!(
+            "{name} must be positive, got {px}"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_order() -> Result<()> {
+        let cloid = Uuid::new_v4();
+        let order = Order::buy("ETH")
+            .size(0.1)
+            .limit(1800)
+            .post_only()
+            .reduce_only()
+            .cloid(cloid)
+            .build()?;
+
+        assert_eq!(order.asset, "ETH");
+        assert!(order.is_buy);
+        assert!(order.reduce_only);
+        assert_eq!(order.sz, 0.1);
+        assert_eq!(order.limit_px, 1800.0);
+        assert_eq!(order.cloid, Some(cloid));
+        match order.order_type {
+            ClientOrder::Limit(limit) => assert_eq!(limit.tif, "Alo"),
+            other => panic!("unexpected order type {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_limit_order_defaults_to_gtc() -> Result<()> {
+        let order = Order::sell("BTC").size(1).limit(60000).build()?;
+        assert!(!order.is_buy);
+        assert!(!order.reduce_only);
+        match order.order_type {
+            ClientOrder::Limit(limit) => assert_eq!(limit.tif, "Gtc"),
+            other => panic!("unexpected order type {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_stop_market_order() -> Result<()> {
+        let order = Order::sell("BTC").size(0.01).stop_market(60000).build()?;
+        assert_eq!(order.limit_px, 60000.0);
+        match order.order_type {
+            ClientOrder::Trigger(trigger) => {
+                assert!(trigger.is_market);
+                assert_eq!(trigger.trigger_px, 60000.0);
+                assert_eq!(trigger.tpsl, "sl");
+            }
+            other => panic!("unexpected order type {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_take_profit_limit_order() -> Result<()> {
+        let order = Order::sell("ETH")
+            .size(0.5)
+            .take_profit_limit(2500)
+            .limit(2490)
+            .build()?;
+        assert_eq!(order.limit_px, 2490.0);
+        match order.order_type {
+            ClientOrder::Trigger(trigger) => {
+                assert!(!trigger.is_market);
+                assert_eq!(trigger.trigger_px, 2500.0);
+                assert_eq!(trigger.tpsl, "tp");
+            }
+            other => panic!("unexpected order type {other:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_combinations() {
+        assert!(matches!(
+            Order::buy("ETH").limit(1800).build(),
+            Err(HyperliquidError::InvalidSize(_))
+        ));
+        assert!(matches!(
+            Order::buy("ETH").size(-1).limit(1800).build(),
+            Err(HyperliquidError::InvalidSize(_))
+        ));
+        assert!(matches!(
+            Order::buy("ETH").size(0.1).build(),
+            Err(HyperliquidError::InvalidPrice(_))
+        ));
+        assert!(matches!(
+            Order::sell("BTC").size(0.1).stop_market(60000).ioc().build(),
+            Err(HyperliquidError::OrderError(_))
+        ));
+        assert!(matches!(
+            Order::sell("BTC").size(0.1).stop_limit(60000).build(),
+            Err(HyperliquidError::InvalidPrice(_))
+        ));
+    }
+}
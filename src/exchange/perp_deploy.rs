@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpAssetRequest {
+    pub coin: String,
+    pub sz_decimals: u32,
+    pub oracle_px: String,
+    pub margin_table_id: u32,
+    pub only_isolated: bool,
+}
+
+/// Parameters of a new perp DEX, given when its first asset is registered.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDexSchema {
+    pub full_name: String,
+    pub collateral_token: u32,
+    /// Lowercase address allowed to set oracle prices, the deployer when `None`.
+    pub oracle_updater: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterAsset {
+    pub max_gas: Option<u64>,
+    pub asset_request: PerpAssetRequest,
+    pub dex: String,
+    pub schema: Option<PerpDexSchema>,
+}
+
+/// Oracle, mark and external prices of a perp DEX as `(coin, px)` pairs sorted by coin.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetOracle {
+    pub dex: String,
+    pub oracle_pxs: Vec<(String, String)>,
+    pub mark_pxs: Vec<Vec<(String, String)>>,
+    pub external_perp_pxs: Vec<(String, String)>,
+}
+
+/// Operations on a HIP-3 builder-deployed perp DEX, sent as a `perpDeploy` action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum PerpDeploy {
+    RegisterAsset(RegisterAsset),
+    SetOracle(SetOracle),
+}
@@ -0,0 +1,263 @@
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::Chain,
+    errors::{HyperliquidError, Result},
+    exchange::actions::{Actions, HYPERLIQUID_EIP_PREFIX},
+    signature::{
+        create_signature::SignatureRsv, eip712::TypedData, l1_action_typed_data, recover_signer,
+        user_signed_action_typed_data, HyperliquidSigner,
+    },
+};
+
+/// An action exported for signing on another machine, such as an air-gapped cold wallet.
+///
+/// The envelope serializes to portable JSON holding everything needed to sign the action: the
+/// hash to sign and the equivalent EIP-712 typed data for wallets that sign typed data
+/// themselves. Signing with [`SigningEnvelope::sign`] is synchronous and needs no network or
+/// async runtime; the signed envelope is submitted later with
+/// [`ExchangeClient::broadcast`](crate::ExchangeClient::broadcast).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningEnvelope {
+    action: Actions,
+    nonce: u64,
+    vault_address: Option<Address>,
+    chain: Chain,
+    hash: B256,
+    typed_data: TypedData,
+    #[serde(default)]
+    signature: Option<SignatureRsv>,
+}
+
+impl SigningEnvelope {
+    /// Prepares `action` for signing. User-signed actions are never sent on behalf of a vault
+    /// and must carry `nonce` as their own nonce.
+    pub fn new(
+        action: Actions,
+        nonce: u64,
+        vault_address: Option<Address>,
+        chain: Chain,
+    ) -> Result<Self> {
+        if matches!(action, Actions::MultiSig(_)) {
+            return Err(HyperliquidError::InvalidParameter(
+                "multi-sig actions are signed through MultiSigTransaction".to_string(),
+            ));
+        }
+        let vault_address = match action.user_signed_nonce() {
+            Some(action_nonce) if action_nonce != nonce => {
+                return Err(HyperliquidError::InvalidParameter(format!(
+                    "action nonce {action_nonce} does not match envelope nonce {nonce}"
+                )));
+            }
+            _ if action.user_signed_type().is_some() => None,
+            _ => vault_address,
+        };
+
+        let (hash, typed_data) = Self::signing_payload(&action, nonce, vault_address, chain)?;
+        Ok(Self {
+            action,
+            nonce,
+            vault_address,
+            chain,
+            hash,
+            typed_data,
+            signature: None,
+        })
+    }
+
+    fn signing_payload(
+        action: &Actions,
+        nonce: u64,
+        vault_address: Option<Address>,
+        chain: Chain,
+    ) -> Result<(B256, TypedData)> {
+        let hash = action.signing_hash(nonce, vault_address, chain.is_mainnet())?;
+        let typed_data = match action.user_signed_type() {
+            Some((primary_type, fields, signature_chain_id)) => user_signed_action_typed_data(
+                &format!("{HYPERLIQUID_EIP_PREFIX}{primary_type}"),
+                fields,
+                &serde_json::to_value(action)?,
+                signature_chain_id,
+            ),
+            None => l1_action_typed_data(action.hash(nonce, vault_address)?, chain.is_mainnet()),
+        };
+        Ok((hash, typed_data))
+    }
+
+    pub fn action(&self) -> &Actions {
+        &self.action
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn vault_address(&self) -> Option<Address> {
+        self.vault_address
+    }
+
+    pub fn chain(&self) -> Chain {
+        self.chain
+    }
+
+    /// The EIP-712 digest the signer signs.
+    pub fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    pub fn typed_data(&self) -> &TypedData {
+        &self.typed_data
+    }
+
+    pub fn signature(&self) -> Option<SignatureRsv> {
+        self.signature
+    }
+
+    /// Fails if the hash or typed data of the envelope do not match its action, e.g. after the
+    /// JSON was edited in transit.
+    pub fn check(&self) -> Result<()> {
+        let (hash, typed_data) =
+            Self::signing_payload(&self.action, self.nonce, self.vault_address, self.chain)?;
+        if hash != self.hash || typed_data != self.typed_data {
+            return Err(HyperliquidError::SignatureError(format!(
+                "envelope hash {} does not match its action, expected {hash}",
+                self.hash
+            )));
+        }
+        Ok(())
+    }
+
+    /// Signs the envelope after checking it against its action.
+    pub fn sign(&mut self, signer: &impl HyperliquidSigner) -> Result<()> {
+        self.check()?;
+        self.signature = Some(signer.sign_hash(self.hash)?.to_rsv());
+        Ok(())
+    }
+
+    /// Attaches a signature made elsewhere, e.g. by a wallet signing [`Self::typed_data`],
+    /// after checking the envelope and that the signature recovers to `signer`.
+    pub fn set_signature(&mut self, signature: SignatureRsv, signer: Address) -> Result<()> {
+        self.check()?;
+        let recovered = recover_signer(self.hash, &signature)?;
+        if recovered != signer {
+            return Err(HyperliquidError::SignatureError(format!(
+                "envelope signed by {recovered}, expected {signer}"
+            )));
+        }
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        constants::ARBITRUM_TESTNET_CHAIN_ID,
+        exchange::{actions::CDeposit, UpdateLeverage},
+        LocalWallet,
+    };
+    use alloy_primitives::U256;
+
+    fn update_leverage() -> Actions {
+        Actions::UpdateLeverage(UpdateLeverage {
+            asset: 1,
+            is_cross: true,
+            leverage: 5,
+        })
+    }
+
+    #[test]
+    fn test_l1_envelope() {
+        let envelope =
+            SigningEnvelope::new(update_leverage(), 1700000000000, None, Chain::Testnet).unwrap();
+        let connection_id = update_leverage().hash(1700000000000, None).unwrap();
+        assert_eq!(
+            envelope.hash(),
+            crate::signature::l1_action_signing_hash(connection_id, false)
+        );
+
+        // The exported typed data hashes to the same digest in any EIP-712 implementation
+        let typed_data: alloy_dyn_abi::TypedData =
+            serde_json::from_value(serde_json::to_value(envelope.typed_data()).unwrap()).unwrap();
+        assert_eq!(typed_data.eip712_signing_hash().unwrap(), envelope.hash());
+        assert_eq!(envelope.typed_data().message["source"], "b");
+    }
+
+    #[test]
+    fn test_user_signed_envelope() {
+        let action = Actions::CDeposit(CDeposit {
+            signature_chain_id: U256::from(ARBITRUM_TESTNET_CHAIN_ID),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100_000_000,
+            nonce: 1700000000000,
+        });
+        let vault = Some(Address::repeat_byte(1));
+        assert!(SigningEnvelope::new(action.clone(), 1, None, Chain::Testnet).is_err());
+
+        let envelope = SigningEnvelope::new(action, 1700000000000, vault, Chain::Testnet).unwrap();
+        assert_eq!(envelope.vault_address(), None);
+        let typed_data = envelope.typed_data();
+        assert_eq!(typed_data.primary_type, "HyperliquidTransaction:CDeposit");
+        assert_eq!(
+            typed_data.message,
+            serde_json::json!({"hyperliquidChain": "Testnet", "wei": 100000000, "nonce": 1700000000000u64})
+        );
+        assert_eq!(typed_data.domain.chain_id, U256::from(ARBITRUM_TESTNET_CHAIN_ID));
+    }
+
+    #[test]
+    fn test_sign_offline() {
+        let wallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let json = SigningEnvelope::new(update_leverage(), 1700000000000, None, Chain::Mainnet)
+            .unwrap()
+            .to_json()
+            .unwrap();
+
+        let mut envelope = SigningEnvelope::from_json(&json).unwrap();
+        envelope.sign(&wallet).unwrap();
+        let signed = SigningEnvelope::from_json(&envelope.to_json().unwrap()).unwrap();
+        assert_eq!(
+            signed.signature(),
+            Some(wallet.sign_hash(envelope.hash()).unwrap().to_rsv())
+        );
+
+        let tampered = json.replace(r#""leverage":5"#, r#""leverage":50"#);
+        assert_ne!(tampered, json);
+        let mut tampered = SigningEnvelope::from_json(&tampered).unwrap();
+        assert!(tampered.sign(&wallet).is_err());
+    }
+
+    #[test]
+    fn test_set_signature() {
+        let wallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let mut envelope =
+            SigningEnvelope::new(update_leverage(), 1700000000000, None, Chain::Testnet).unwrap();
+        let signature = wallet.sign_hash(envelope.hash()).unwrap().to_rsv();
+
+        assert!(matches!(
+            envelope.set_signature(signature, Address::repeat_byte(1)),
+            Err(HyperliquidError::SignatureError(_))
+        ));
+        let other_hash = wallet.sign_hash(B256::repeat_byte(1)).unwrap().to_rsv();
+        assert!(envelope.set_signature(other_hash, wallet.address()).is_err());
+        assert_eq!(envelope.signature(), None);
+
+        envelope.set_signature(signature, wallet.address()).unwrap();
+        assert_eq!(envelope.signature(), Some(signature));
+    }
+}
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSpec {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterToken {
+    pub spec: TokenSpec,
+    pub max_gas: u64,
+    pub full_name: String,
+}
+
+/// Genesis balances, as `(lowercase address, wei)` pairs for users and `(token, wei)` pairs
+/// for holders of an existing token.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserGenesis {
+    pub token: u32,
+    pub user_and_wei: Vec<(String, String)>,
+    pub existing_token_and_wei: Vec<(u32, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Genesis {
+    pub token: u32,
+    pub max_supply: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_hyperliquidity: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterSpot {
+    pub tokens: [u32; 2],
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterHyperliquidity {
+    pub spot: u32,
+    pub start_px: String,
+    pub order_sz: String,
+    pub n_orders: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_seeded_levels: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetDeployerTradingFeeShare {
+    pub token: u32,
+    pub share: String,
+}
+
+/// Steps of a HIP-1 spot token deployment, sent as a `spotDeploy` action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum SpotDeploy {
+    #[serde(rename = "registerToken2")]
+    RegisterToken(RegisterToken),
+    UserGenesis(UserGenesis),
+    Genesis(Genesis),
+    RegisterSpot(RegisterSpot),
+    RegisterHyperliquidity(RegisterHyperliquidity),
+    SetDeployerTradingFeeShare(SetDeployerTradingFeeShare),
+}
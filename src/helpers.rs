@@ -3,7 +3,16 @@ use chrono::prelude::Utc;
 use lazy_static::lazy_static;
 use log::info;
 use rand::{thread_rng, Rng};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    collections::BTreeSet,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use tokio::time::Instant;
 use uuid::Uuid;
 
 fn now_timestamp_ms() -> u64 {
@@ -24,6 +33,80 @@ pub(crate) fn next_nonce() -> u64 {
     nonce
 }
 
+/// How long the exchange accepts a nonce after its timestamp; older nonces can never be used.
+const NONCE_VALIDITY_MS: u64 = 2 * 24 * 60 * 60 * 1000;
+
+/// Hands out nonces and remembers them for as long as the exchange would accept them, so the
+/// nonces of actions that may have leaked can be burned later.
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    issued: Mutex<BTreeSet<u64>>,
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next_nonce(&self) -> u64 {
+        let nonce = next_nonce();
+        let mut issued = self.issued.lock().unwrap_or_else(|e| e.into_inner());
+        issued.insert(nonce);
+        let valid = issued.split_off(&nonce.saturating_sub(NONCE_VALIDITY_MS));
+        *issued = valid;
+        nonce
+    }
+
+    /// The nonces handed out within `window`, in milliseconds since the epoch.
+    pub fn issued_between(&self, window: RangeInclusive<u64>) -> Vec<u64> {
+        let issued = self.issued.lock().unwrap_or_else(|e| e.into_inner());
+        issued.range(window).copied().collect()
+    }
+}
+
+/// Token bucket shared by the requests of one or more clients, so that together they stay under
+/// the per-minute request budget of the API.
+#[derive(Debug)]
+pub struct RateLimiter {
+    per_minute: u32,
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute: per_minute.max(1),
+            bucket: Mutex::new((per_minute.max(1) as f64, Instant::now())),
+        }
+    }
+
+    /// Waits until a request may be sent and takes it out of the budget.
+    pub async fn acquire(&self) {
+        let per_ms = self.per_minute as f64 / 60_000.0;
+        loop {
+            let wait_ms = {
+                let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+                let now = Instant::now();
+                let elapsed_ms = now.duration_since(bucket.1).as_secs_f64() * 1000.0;
+                bucket.0 = (bucket.0 + elapsed_ms * per_ms).min(self.per_minute as f64);
+                bucket.1 = now;
+                if bucket.0 >= 1.0 {
+                    bucket.0 -= 1.0;
+                    return;
+                }
+                (1.0 - bucket.0) / per_ms
+            };
+            tokio::time::sleep(Duration::from_secs_f64(wait_ms / 1000.0)).await;
+        }
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(RATE_LIMIT_PER_MINUTE)
+    }
+}
+
 pub(crate) const WIRE_DECIMALS: u8 = 8;
 
 pub(crate) fn float_to_string_for_hashing(x: f64) -> String {
@@ -68,6 +151,56 @@ pub fn truncate_float(value: f64, decimals: u32, round_down: bool) -> f64 {
     }
 }
 
+/// Rounds `px` to a price the venue accepts: at most five significant figures and at most
+/// `6 - sz_decimals` decimals for perps (`8 - sz_decimals` for spot). Integer prices are always
+/// accepted as is.
+pub fn round_px(px: f64, sz_decimals: u32, is_spot: bool) -> f64 {
+    if px == 0.0 || px.fract() == 0.0 {
+        return px;
+    }
+    let max_decimals = if is_spot {
+        MAX_SPOT_PX_DECIMALS
+    } else {
+        MAX_PERP_PX_DECIMALS
+    };
+    let magnitude = px.abs().log10().floor() as i32;
+    let decimals = (MAX_PX_SIG_FIGS - 1 - magnitude)
+        .min(max_decimals.saturating_sub(sz_decimals) as i32)
+        .max(0);
+    round_to_decimals(px, decimals as u32)
+}
+
+/// Rounds `sz` to the asset's size decimals.
+pub fn round_sz(sz: f64, sz_decimals: u32) -> f64 {
+    round_to_decimals(sz, sz_decimals)
+}
+
+/// Converts a decimal string such as `"1.5"` to an integer amount with `decimals` decimals.
+/// Returns `None` when the amount is malformed, negative, has more decimals than allowed or
+/// does not fit in a `u64`.
+pub(crate) fn decimal_to_wei(amount: &str, decimals: u32) -> Option<u64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || fraction.len() > decimals as usize
+        || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>().ok()? * 10u64.pow(decimals - fraction.len() as u32)
+    };
+    whole.checked_mul(10u64.checked_pow(decimals)?)?.checked_add(fraction)
+}
+
+fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    let multiplier = 10f64.powi(decimals as i32);
+    (value * multiplier).round() / multiplier
+}
+
 pub fn bps_diff(px1: f64, px2: f64) -> u16 {
     if px2 < EPSILON {
         return INF_BPS;
@@ -114,6 +247,38 @@ lazy_static! {
 mod tests {
     use super::*;
 
+    #[test]
+    fn round_px_test() {
+        assert_eq!(round_px(1800.0, 4, false), 1800.0);
+        assert_eq!(round_px(123456.7, 0, false), 123457.0);
+        assert_eq!(round_px(1234.567, 1, false), 1234.6);
+        assert_eq!(round_px(0.123456789, 0, false), 0.12346);
+        assert_eq!(round_px(0.0000237812, 0, true), 0.00002378);
+        assert_eq!(round_px(0.0000237812, 2, true), 0.000024);
+        assert_eq!(round_px(0.0000237812, 0, false), 0.000024);
+    }
+
+    #[test]
+    fn decimal_to_wei_test() {
+        assert_eq!(decimal_to_wei("1", 8), Some(100_000_000));
+        assert_eq!(decimal_to_wei("1.5", 8), Some(150_000_000));
+        assert_eq!(decimal_to_wei("0.00000001", 8), Some(1));
+        assert_eq!(decimal_to_wei(".25", 2), Some(25));
+        assert_eq!(decimal_to_wei("12060.16529862", 8), Some(1_206_016_529_862));
+        assert_eq!(decimal_to_wei("0.000000001", 8), None);
+        assert_eq!(decimal_to_wei("-1", 8), None);
+        assert_eq!(decimal_to_wei("1e5", 8), None);
+        assert_eq!(decimal_to_wei("", 8), None);
+        assert_eq!(decimal_to_wei(".", 8), None);
+        assert_eq!(decimal_to_wei("184467440738", 8), None);
+    }
+
+    #[test]
+    fn round_sz_test() {
+        assert_eq!(round_sz(0.123456, 4), 0.1235);
+        assert_eq!(round_sz(1000000.4, 0), 1000000.0);
+    }
+
     #[test]
     fn float_to_string_for_hashing_test() {
         assert_eq!(float_to_string_for_hashing(0.), "0".to_string());
@@ -154,4 +319,33 @@ mod tests {
             "987654321".to_string()
         );
     }
+
+    #[test]
+    fn nonce_manager_test() {
+        let manager = NonceManager::new();
+        let first = manager.next_nonce();
+        let second = manager.next_nonce();
+        assert!(second > first);
+        assert_eq!(manager.issued_between(first..=second), vec![first, second]);
+        assert_eq!(manager.issued_between(second..=u64::MAX), vec![second]);
+        assert!(manager.issued_between(0..=first - 1).is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limiter_test() {
+        let limiter = RateLimiter::new(600);
+        let start = Instant::now();
+        for _ in 0..600 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        // The budget refills one request every 100ms
+        let mut acquire = Box::pin(limiter.acquire());
+        assert!(futures_util::poll!(&mut acquire).is_pending());
+        tokio::time::advance(Duration::from_millis(99)).await;
+        assert!(futures_util::poll!(&mut acquire).is_pending());
+        tokio::time::advance(Duration::from_millis(2)).await;
+        assert!(futures_util::poll!(&mut acquire).is_ready());
+    }
 }
@@ -1,111 +1,112 @@
 use crate::{
     info::{
         response_structs::{
-            CandlesSnapshotResponse, L2SnapshotResponse, OpenOrdersResponse,
-            OrderStatusResponse, RecentTradesResponse, UserFillsResponse, UserStateResponse,
-            UserFeesResponse, UserStatesResponse, UserTokenBalancesResponse, SpotMetaAndAssetContextsResponse,
-            ReferralResponse, MetaResponse, SpotMetaResponse, StakingInfoResponse, UserStakingInfoResponse,
-            StakingRewardsInfoResponse,
+            L2SnapshotResponse, OrderStatusResponse, UserFeesResponse, UserTokenBalanceResponse,
+            ReferralResponse, UserFundingResponse,
+        },
+        queries::{self, InfoQuery, OrderRef},
+        types::{FundingRate, Fill, Trade},
+        sub_structs::{
+            UserState, OpenOrder, OrderInfo, BasicOrderInfo, UserTokenBalance, SpotDeployState, TokenDetails, DelegatorSummary,
+            Delegation, DelegatorReward, DelegatorHistoryEntry, ValidatorSummary, VaultDetails,
+            UserVaultEquity, PredictedFunding, Portfolio, UserRole, UserRateLimit, ExtraAgent,
         },
-        types::{UserFee, UserTokenBalance, FundingRate, SpotMetaAndAssetContexts, Candle, Fill, Trade, StakingInfo, UserStakingInfo, StakingRewardsInfo},
-        sub_structs::{UserState, OrderInfo},
     },
-    meta::{Meta, SpotMeta},
+    exchange::MultiSigSigners,
+    meta::{Meta, MetaAndAssetCtxs, PerpDex, SpotMeta, SpotMetaAndAssetCtxs, SpotValuation},
     errors::{HyperliquidError, Result},
-    BaseUrl,
-    ws::{Subscription, WsManager},
+    BaseUrl, RateLimiter,
+    ws::{CandleData, CandleInterval, L2BookOptions, Subscription, WsManager},
     Message,
     req::HttpClient,
 };
 
-use alloy_primitives::Address;
+use alloy_primitives::{hex, Address, U128};
+use futures_util::stream::{self, Stream, TryStreamExt};
+use std::future::Future;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    sync::Arc,
+};
 use tokio::sync::mpsc::UnboundedSender;
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct CandleSnapshotRequest {
-    pub coin: String,
-    pub interval: String,
-    pub start_time: u64,
-    pub end_time: u64,
-}
-
-#[derive(Deserialize, Serialize, Debug, Clone)]
-#[serde(tag = "type")]
-#[serde(rename_all = "camelCase")]
-pub(crate) enum InfoRequest {
-    #[serde(rename = "clearinghouseState")]
-    UserState {
-        user: Address,
-    },
-    #[serde(rename = "batchClearinghouseStates")]
-    UserStates {
-        users: Vec<Address>,
-    },
-    #[serde(rename = "spotClearinghouseState")]
-    UserTokenBalances {
-        user: Address,
-    },
-    UserFees {
-        user: Address,
-    },
-    OpenOrders {
-        user: Address,
-    },
-    OrderStatus {
-        user: Address,
-        oid: u64,
-    },
-    Meta,
-    SpotMeta,
-    SpotMetaAndAssetCtxs,
-    AllMids,
-    UserFills {
-        user: Address,
-    },
-    #[serde(rename_all = "camelCase")]
-    FundingHistory {
-        coin: String,
-        start_time: u64,
-        end_time: Option<u64>,
-    },
-    #[serde(rename_all = "camelCase")]
-    UserFunding {
-        user: Address,
-        start_time: u64,
-        end_time: Option<u64>,
-    },
-    L2Book {
-        coin: String,
-    },
-    RecentTrades {
-        coin: String,
-    },
-    #[serde(rename_all = "camelCase")]
-    CandleSnapshot {
-        req: CandleSnapshotRequest,
-    },
-    Referral {
-        user: Address,
-    },
-    HistoricalOrders {
-        user: Address,
-    },
-    StakingInfo,
-    UserStakingInfo {
-        user: Address,
-    },
-    StakingRewardsInfo,
-}
-
 #[derive(Debug)]
 pub struct InfoClient {
     pub(crate) http_client: HttpClient,
     pub(crate) ws_manager: Option<WsManager>,
     reconnect: bool,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+/// Most fills `userFillsByTime` returns for one request.
+const USER_FILLS_PAGE_LIMIT: usize = 2000;
+/// Most entries `fundingHistory` and `userFunding` return for one request.
+const FUNDING_PAGE_LIMIT: usize = 500;
+/// Most candles `candleSnapshot` returns for one request.
+const CANDLE_LIMIT: u64 = 5000;
+
+/// Streams the entries of a time-windowed endpoint within `start..=end`, oldest first. `fetch`
+/// returns the entries from a start time on; a full page continues from the time of its last
+/// entry, and entries already returned at that time are recognised by `key` and skipped. A full
+/// page within a single millisecond can't be paged any further, so the stream ends with an
+/// error rather than silently dropping the rest of that millisecond.
+fn paginate_by_time<'a, T, K, F, Fut>(
+    start: u64,
+    end: u64,
+    page_limit: usize,
+    fetch: F,
+    key: fn(&T) -> (u64, K),
+) -> impl Stream<Item = Result<T>> + 'a
+where
+    T: 'a,
+    K: Eq + std::hash::Hash + 'a,
+    F: Fn(u64) -> Fut + 'a,
+    Fut: Future<Output = Result<Vec<T>>> + 'a,
+{
+    let cursor = Some((start, HashSet::new()));
+    stream::try_unfold(cursor, move |cursor| {
+        let page = cursor.as_ref().filter(|(start, _)| *start <= end).map(|(start, _)| fetch(*start));
+        async move {
+            let (Some((start, boundary_keys)), Some(page)) = (cursor, page) else {
+                return Ok::<_, HyperliquidError>(None);
+            };
+            let mut page = page.await?;
+            let full = page.len() >= page_limit;
+            page.sort_by_key(|entry| key(entry).0);
+
+            let mut truncated = None;
+            let next = match page.last() {
+                Some(last) if full => {
+                    let last_time = key(last).0;
+                    if last_time > start {
+                        let keys = page
+                            .iter()
+                            .map(key)
+                            .filter(|(time, _)| *time == last_time)
+                            .map(|(_, id)| id)
+                            .collect();
+                        Some((last_time, keys))
+                    } else {
+                        truncated = Some(HyperliquidError::InvalidResponse(format!(
+                            "more than {page_limit} entries at {start} ms, the rest of that \
+                             millisecond can not be fetched"
+                        )));
+                        None
+                    }
+                }
+                _ => None,
+            };
+            page.retain(|entry| {
+                let (time, id) = key(entry);
+                time != start || !boundary_keys.contains(&id)
+            });
+            let entries = page.into_iter().map(Ok).chain(truncated.map(Err));
+            Ok(Some((stream::iter(entries), next)))
+        }
+    })
+    .try_flatten()
 }
 
 impl InfoClient {
@@ -114,9 +115,17 @@ impl InfoClient {
             http_client: HttpClient::new(url),
             ws_manager: None,
             reconnect: false,
+            rate_limiter: Arc::new(RateLimiter::default()),
         }
     }
 
+    /// Sends every request of this client through `rate_limiter`, which may be shared with other
+    /// clients using the same IP.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
     pub async fn new_with_ws(url: String) -> Result<Self> {
         let mut client = Self::new(url.clone());
         client.ws_manager = Some(WsManager::new(url, true).await?);
@@ -129,6 +138,10 @@ impl InfoClient {
         subscription: Subscription,
         sender_channel: UnboundedSender<Message>,
     ) -> Result<u32> {
+        if let Subscription::L2Book { options, .. } = &subscription {
+            options.validate()?;
+        }
+
         if self.ws_manager.is_none() {
             let ws_manager = WsManager::new(
                 format!("ws{}/ws", &self.http_client.base_url[4..]),
@@ -169,67 +182,145 @@ impl InfoClient {
             .map_err(HyperliquidError::from)
     }
 
-    async fn send_info_request<T: for<'a> Deserialize<'a>>(
-        &self,
-        info_request: InfoRequest,
-    ) -> Result<T> {
+    /// Sends `query` through this client's rate limiter and deserializes its response.
+    pub async fn query<Q: InfoQuery>(&self, query: &Q) -> Result<Q::Response> {
         let data =
-            serde_json::to_string(&info_request).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
+            serde_json::to_string(query).map_err(|e| HyperliquidError::SerializationError(e.to_string()))?;
 
+        self.rate_limiter.acquire().await;
         let return_data = self.http_client.post("/info", data).await?;
         serde_json::from_str(&return_data).map_err(|e| HyperliquidError::SerializationError(e.to_string()))
     }
 
-    pub async fn open_orders(&self, address: Address) -> Result<Vec<OrderInfo>> {
-        let input = InfoRequest::OpenOrders { user: address };
-        let response: OpenOrdersResponse = self.send_info_request(input).await?;
-        Ok(response.data)
+    /// Sends `request` as is and returns the untyped response, for exploring queries the SDK
+    /// doesn't model yet.
+    pub async fn info_raw(&self, request: serde_json::Value) -> Result<serde_json::Value> {
+        self.query(&request).await
+    }
+
+    /// Open orders of `address` on the perp DEX `dex`, or the default DEX when `None`.
+    pub async fn open_orders(&self, address: Address, dex: Option<String>) -> Result<Vec<OpenOrder>> {
+        let input = queries::OpenOrders { user: address, dex };
+        self.query(&input).await
     }
 
-    pub async fn user_state(&self, address: Address) -> Result<UserStateResponse> {
-        let input = InfoRequest::UserState { user: address };
-        self.send_info_request(input).await
+    /// Margin, withdrawable balance and positions of `address` on the perp DEX `dex`, or the
+    /// default DEX when `None`.
+    pub async fn user_state(&self, address: Address, dex: Option<String>) -> Result<UserState> {
+        let input = queries::ClearinghouseState { user: address, dex };
+        self.query(&input).await
     }
 
     pub async fn user_states(&self, addresses: Vec<Address>) -> Result<Vec<UserState>> {
-        let response: UserStatesResponse = self.send_info_request(InfoRequest::UserStates { users: addresses.clone() }).await?;
-        Ok(response.data)
+        self.query(&queries::BatchClearinghouseStates { users: addresses }).await
     }
 
     pub async fn user_token_balances(&self, address: Address) -> Result<Vec<UserTokenBalance>> {
-        let response: UserTokenBalancesResponse = self.send_info_request(InfoRequest::UserTokenBalances { user: address }).await?;
-        Ok(response.data)
+        let response: UserTokenBalanceResponse = self.query(&queries::SpotClearinghouseState { user: address }).await?;
+        Ok(response.balances)
+    }
+
+    /// Value of `address`'s spot balances in USDC, priced at the current spot mids.
+    pub async fn spot_balances_usdc_value(&self, address: Address) -> Result<SpotValuation> {
+        let (balances, spot_meta, mids) = tokio::try_join!(
+            self.user_token_balances(address),
+            self.get_spot_meta(),
+            self.all_mids(None),
+        )?;
+        spot_meta.usdc_value(&balances, &mids)
+    }
+
+    pub async fn user_fees(&self, address: Address) -> Result<UserFeesResponse> {
+        self.query(&queries::UserFees { user: address }).await
+    }
+
+    /// Whether `address` is a user, an agent, a vault or a sub-account, and whom it acts for.
+    pub async fn user_role(&self, address: Address) -> Result<UserRole> {
+        self.query(&queries::UserRole { user: address }).await
+    }
+
+    /// Requests `address` has used and may send in total.
+    pub async fn user_rate_limit(&self, address: Address) -> Result<UserRateLimit> {
+        self.query(&queries::UserRateLimit { user: address }).await
+    }
+
+    /// Named agents `address` has approved.
+    pub async fn extra_agents(&self, address: Address) -> Result<Vec<ExtraAgent>> {
+        self.query(&queries::ExtraAgents { user: address }).await
+    }
+
+    pub async fn is_vip(&self, address: Address) -> Result<bool> {
+        let response: Option<bool> = self.query(&queries::IsVip { user: address }).await?;
+        Ok(response.unwrap_or(false))
     }
 
-    pub async fn user_fees(&self, address: Address) -> Result<UserFee> {
-        let response: UserFeesResponse = self.send_info_request(InfoRequest::UserFees { user: address }).await?;
-        Ok(response.data)
+    /// Meta of the perp DEX `dex`, or the default DEX when `None`.
+    pub async fn get_meta(&self, dex: Option<String>) -> Result<Meta> {
+        self.query(&queries::Meta { dex }).await
     }
 
-    pub async fn get_meta(&self) -> Result<Meta> {
-        let response: MetaResponse = self.send_info_request(InfoRequest::Meta).await?;
-        Ok(response.meta)
+    /// All perp DEXs. The first entry is `None` and stands for the default DEX.
+    pub async fn perp_dexs(&self) -> Result<Vec<Option<PerpDex>>> {
+        self.query(&queries::PerpDexs {}).await
     }
 
     pub async fn get_spot_meta(&self) -> Result<SpotMeta> {
-        let response: SpotMetaResponse = self.send_info_request(InfoRequest::SpotMeta).await?;
-        Ok(response.spot_meta)
+        self.query(&queries::SpotMeta {}).await
+    }
+
+    /// Meta of the default perp DEX with the current funding, open interest, oracle and mark
+    /// prices of each asset.
+    pub async fn meta_and_asset_ctxs(&self) -> Result<MetaAndAssetCtxs> {
+        self.query(&queries::MetaAndAssetCtxs {}).await
     }
 
-    pub async fn spot_meta_and_asset_contexts(&self) -> Result<SpotMetaAndAssetContexts> {
-        let response: SpotMetaAndAssetContextsResponse = self.send_info_request(InfoRequest::SpotMetaAndAssetCtxs).await?;
-        Ok(response.data)
+    pub async fn spot_meta_and_asset_contexts(&self) -> Result<Vec<SpotMetaAndAssetCtxs>> {
+        self.query(&queries::SpotMetaAndAssetCtxs {}).await
     }
 
-    pub async fn all_mids(&self) -> Result<HashMap<String, String>> {
-        let input = InfoRequest::AllMids;
-        self.send_info_request(input).await
+    pub async fn all_mids(&self, dex: Option<String>) -> Result<HashMap<String, String>> {
+        let input = queries::AllMids { dex };
+        self.query(&input).await
     }
 
     pub async fn user_fills(&self, address: Address) -> Result<Vec<Fill>> {
-        let input = InfoRequest::UserFills { user: address };
-        let response: UserFillsResponse = self.send_info_request(input).await?;
-        Ok(response.data)
+        let input = queries::UserFills { user: address };
+        self.query(&input).await
+    }
+
+    /// Fills of `user` between `start_time` and `end_time`, at most 2000 per request. With
+    /// `aggregate_by_time`, partial fills of one crossing order are combined.
+    pub async fn user_fills_by_time(
+        &self,
+        user: Address,
+        start_time: u64,
+        end_time: Option<u64>,
+        aggregate_by_time: Option<bool>,
+    ) -> Result<Vec<Fill>> {
+        let input = queries::UserFillsByTime {
+            user,
+            start_time,
+            end_time,
+            aggregate_by_time,
+        };
+        self.query(&input).await
+    }
+
+    /// All fills of `user` within `range`, in milliseconds since the epoch, oldest first. Pages
+    /// through `userFillsByTime` as needed and skips fills repeated at page boundaries.
+    pub fn fills_stream(
+        &self,
+        user: Address,
+        range: RangeInclusive<u64>,
+    ) -> impl Stream<Item = Result<Fill>> + '_ {
+        let (start, end) = range.into_inner();
+        paginate_by_time(
+            start,
+            end,
+            USER_FILLS_PAGE_LIMIT,
+            move |start| self.user_fills_by_time(user, start, Some(end), None),
+            |fill| (fill.time, fill.tid),
+        )
     }
 
     pub async fn funding_history(
@@ -238,12 +329,28 @@ impl InfoClient {
         start_time: u64,
         end_time: Option<u64>,
     ) -> Result<Vec<FundingRate>> {
-        let input = InfoRequest::FundingHistory {
+        let input = queries::FundingHistory {
             coin,
             start_time,
             end_time,
         };
-        self.send_info_request(input).await
+        self.query(&input).await
+    }
+
+    /// All funding rates of `coin` within `range`, in milliseconds since the epoch, oldest first.
+    pub fn funding_history_stream(
+        &self,
+        coin: String,
+        range: RangeInclusive<u64>,
+    ) -> impl Stream<Item = Result<FundingRate>> + '_ {
+        let (start, end) = range.into_inner();
+        paginate_by_time(
+            start,
+            end,
+            FUNDING_PAGE_LIMIT,
+            move |start| self.funding_history(coin.clone(), start, Some(end)),
+            |rate| (rate.time, ()),
+        )
     }
 
     pub async fn user_funding_history(
@@ -251,391 +358,1316 @@ impl InfoClient {
         user: Address,
         start_time: u64,
         end_time: Option<u64>,
-    ) -> Result<Vec<FundingRate>> {
-        let input = InfoRequest::UserFunding {
+    ) -> Result<Vec<UserFundingResponse>> {
+        let input = queries::UserFunding {
             user,
             start_time,
             end_time,
         };
-        self.send_info_request(input).await
+        self.query(&input).await
     }
 
-    pub async fn recent_trades(&self, coin: String) -> Result<Vec<Trade>> {
-        let input = InfoRequest::RecentTrades { coin };
-        let response: RecentTradesResponse = self.send_info_request(input).await?;
-        Ok(response.data)
+    /// All funding payments of `user` within `range`, in milliseconds since the epoch, oldest
+    /// first.
+    pub fn user_funding_stream(
+        &self,
+        user: Address,
+        range: RangeInclusive<u64>,
+    ) -> impl Stream<Item = Result<UserFundingResponse>> + '_ {
+        let (start, end) = range.into_inner();
+        paginate_by_time(
+            start,
+            end,
+            FUNDING_PAGE_LIMIT,
+            move |start| self.user_funding_history(user, start, Some(end)),
+            |funding| (funding.time, funding.delta.coin.clone()),
+        )
     }
 
-    pub async fn l2_snapshot(&self, coin: String) -> Result<L2SnapshotResponse> {
-        let input = InfoRequest::L2Book { coin };
-        self.send_info_request(input).await
+    /// The next funding of each coin, keyed by coin and then by venue (e.g. `HlPerp`,
+    /// `BinPerp`). Venues that don't list a coin are left out.
+    pub async fn predicted_fundings(&self) -> Result<HashMap<String, HashMap<String, PredictedFunding>>> {
+        let response = self.query(&queries::PredictedFundings {}).await?;
+        Ok(response
+            .into_iter()
+            .map(|(coin, venues)| {
+                let venues = venues
+                    .into_iter()
+                    .filter_map(|(venue, funding)| Some((venue, funding?)))
+                    .collect();
+                (coin, venues)
+            })
+            .collect())
     }
 
-    async fn get_candles_snapshot(
-        &self,
-        coin: String,
-        interval: String,
-        start_time: u64,
-        end_time: u64,
-    ) -> Result<CandlesSnapshotResponse> {
-        let input = InfoRequest::CandleSnapshot {
-            req: CandleSnapshotRequest {
-                coin,
-                interval,
-                start_time,
-                end_time,
-            },
-        };
-        self.send_info_request(input).await
+    pub async fn recent_trades(&self, coin: String) -> Result<Vec<Trade>> {
+        let input = queries::RecentTrades { coin };
+        self.query(&input).await
+    }
+
+    /// Book of `coin`, aggregated as `options` asks; see [`L2BookOptions`].
+    pub async fn l2_snapshot(&self, coin: String, options: L2BookOptions) -> Result<L2SnapshotResponse> {
+        options.validate()?;
+        let input = queries::L2Book { coin, options };
+        self.query(&input).await
     }
 
+    /// Candles of `coin` opened between `start_time` and `end_time`, oldest first. Ranges longer
+    /// than one request can cover are fetched in consecutive chunks and merged.
     pub async fn candles_snapshot(
         &self,
         coin: String,
-        interval: String,
+        interval: CandleInterval,
         start_time: u64,
         end_time: u64,
-    ) -> Result<Vec<Candle>> {
-        let response: CandlesSnapshotResponse = self.get_candles_snapshot(coin, interval, start_time, end_time).await?;
-        Ok(response.data)
+    ) -> Result<Vec<CandleData>> {
+        let chunk_ms = interval.duration_ms() * CANDLE_LIMIT;
+        let mut candles: Vec<CandleData> = Vec::new();
+        let mut chunk_start = start_time;
+        while chunk_start <= end_time {
+            let chunk_end = end_time.min(chunk_start.saturating_add(chunk_ms - 1));
+            let input = queries::CandleSnapshot {
+                req: queries::CandleSnapshotRequest {
+                    coin: coin.clone(),
+                    interval,
+                    start_time: chunk_start,
+                    end_time: chunk_end,
+                },
+            };
+            let chunk: Vec<CandleData> = self.query(&input).await?;
+            candles.extend(chunk);
+            if chunk_end == u64::MAX {
+                break;
+            }
+            chunk_start = chunk_end + 1;
+        }
+        candles.sort_by_key(|candle| candle.time_open);
+        candles.dedup_by_key(|candle| candle.time_open);
+        Ok(candles)
     }
 
     pub async fn query_order_by_oid(&self, user: Address, oid: u64) -> Result<OrderInfo> {
-        let input = InfoRequest::OrderStatus {
-            user,
-            oid,
-        };
-        let response: OrderStatusResponse = self.send_info_request(input).await?;
-        Ok(response.data)
+        self.order_status(user, OrderRef::Oid(oid))
+            .await?
+            .ok_or_else(|| HyperliquidError::InvalidParameter(format!("unknown oid {oid}")))
+    }
+
+    /// Status of one of `user`'s orders, or `None` when the user has no such order.
+    pub async fn order_status(&self, user: Address, order: OrderRef) -> Result<Option<OrderInfo>> {
+        let input = queries::OrderStatusQuery { user, oid: order };
+        match self.query(&input).await? {
+            OrderStatusResponse::Order { order } => Ok(Some(*order)),
+            OrderStatusResponse::UnknownOid => Ok(None),
+        }
+    }
+
+    /// Open orders of `address` with their trigger conditions, TP/SL flags and children, on the
+    /// perp DEX `dex` or the default DEX when `None`.
+    pub async fn frontend_open_orders(&self, address: Address, dex: Option<String>) -> Result<Vec<BasicOrderInfo>> {
+        let input = queries::FrontendOpenOrders { user: address, dex };
+        self.query(&input).await
     }
 
     pub async fn query_referral_state(&self, address: Address) -> Result<ReferralResponse> {
-        let input = InfoRequest::Referral { user: address };
-        self.send_info_request(input).await
+        let input = queries::Referral { user: address };
+        self.query(&input).await
     }
 
     pub async fn historical_orders(&self, address: Address) -> Result<Vec<OrderInfo>> {
-        let input = InfoRequest::HistoricalOrders { user: address };
-        self.send_info_request(input).await
+        let input = queries::HistoricalOrders { user: address };
+        self.query(&input).await
+    }
+
+    /// Maximum fee, in tenths of a basis point, that `user` has approved `builder` to charge.
+    pub async fn max_builder_fee(&self, user: Address, builder: Address) -> Result<u64> {
+        let input = queries::MaxBuilderFee { user, builder };
+        self.query(&input).await
+    }
+
+    pub async fn spot_deploy_state(&self, user: Address) -> Result<SpotDeployState> {
+        let input = queries::SpotDeployState { user };
+        self.query(&input).await
+    }
+
+    /// Details of the spot token with `token_id`, as listed in [`SpotMeta::tokens`].
+    pub async fn token_details(&self, token_id: U128) -> Result<TokenDetails> {
+        let input = queries::TokenDetails {
+            token_id: hex::encode_prefixed(token_id.to_be_bytes::<16>()),
+        };
+        self.query(&input).await
+    }
+
+    /// Staking balances of `user`: delegated, undelegated and pending withdrawal.
+    pub async fn delegator_summary(&self, user: Address) -> Result<DelegatorSummary> {
+        let input = queries::DelegatorSummary { user };
+        self.query(&input).await
+    }
+
+    pub async fn delegations(&self, user: Address) -> Result<Vec<Delegation>> {
+        let input = queries::Delegations { user };
+        self.query(&input).await
+    }
+
+    pub async fn delegator_rewards(&self, user: Address) -> Result<Vec<DelegatorReward>> {
+        let input = queries::DelegatorRewards { user };
+        self.query(&input).await
+    }
+
+    pub async fn delegator_history(&self, user: Address) -> Result<Vec<DelegatorHistoryEntry>> {
+        let input = queries::DelegatorHistory { user };
+        self.query(&input).await
     }
 
-    pub async fn get_staking_info(&self) -> Result<StakingInfo> {
-        let response: StakingInfoResponse = self.send_info_request(InfoRequest::StakingInfo).await?;
-        Ok(response.staking_info)
+    pub async fn validator_summaries(&self) -> Result<Vec<ValidatorSummary>> {
+        self.query(&queries::ValidatorSummaries {}).await
     }
 
-    pub async fn get_user_staking_info(&self, address: Address) -> Result<UserStakingInfo> {
-        let input = InfoRequest::UserStakingInfo { user: address };
-        let response: UserStakingInfoResponse = self.send_info_request(input).await?;
-        Ok(response.user_staking_info)
+    /// Details of `vault`, including `user`'s follower state when given.
+    pub async fn vault_details(&self, vault: Address, user: Option<Address>) -> Result<VaultDetails> {
+        let input = queries::VaultDetails {
+            vault_address: vault,
+            user,
+        };
+        self.query(&input).await
     }
 
-    pub async fn get_staking_rewards_info(&self) -> Result<StakingRewardsInfo> {
-        let response: StakingRewardsInfoResponse = self.send_info_request(InfoRequest::StakingRewardsInfo).await?;
-        Ok(response.staking_rewards_info)
+    /// Account value and pnl history of `user` over each period, combined and perp-only.
+    pub async fn portfolio(&self, user: Address) -> Result<Portfolio> {
+        self.query(&queries::Portfolio { user }).await
+    }
+
+    /// Equity `user` holds in each vault they deposited into.
+    pub async fn user_vault_equities(&self, user: Address) -> Result<Vec<UserVaultEquity>> {
+        let input = queries::UserVaultEquities { user };
+        self.query(&input).await
+    }
+
+    /// Authorized users and threshold of `user`, `None` unless it is a multi-sig user.
+    pub async fn user_to_multi_sig_signers(&self, user: Address) -> Result<Option<MultiSigSigners>> {
+        let input = queries::UserToMultiSigSigners { user };
+        self.query(&input).await
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::info::{DelegatorDelta, PortfolioPeriod, ReferrerState, VaultRelationship};
+    use crate::ws::OrderStatus;
+    use futures_util::StreamExt;
+    use serde::{Deserialize, Serialize};
+    use uuid::Uuid;
     use std::str::FromStr;
 
     const TEST_ADDRESS: &str = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414";
-
-    fn setup_mock_server() -> (InfoClient, mockito::ServerGuard) {
-        let mut server = mockito::Server::new();
+    // Responses in the shape the live API returns them, one file per query.
+    const CLEARINGHOUSE_STATE: &str = include_str!("fixtures/clearinghouse_state.json");
+    const SPOT_CLEARINGHOUSE_STATE: &str = include_str!("fixtures/spot_clearinghouse_state.json");
+    const OPEN_ORDERS: &str = include_str!("fixtures/open_orders.json");
+    const HISTORICAL_ORDERS: &str = include_str!("fixtures/historical_orders.json");
+    const USER_FILLS: &str = include_str!("fixtures/user_fills.json");
+    const RECENT_TRADES: &str = include_str!("fixtures/recent_trades.json");
+    const ALL_MIDS: &str = include_str!("fixtures/all_mids.json");
+    const META: &str = include_str!("fixtures/meta.json");
+    const META_AND_ASSET_CTXS: &str = include_str!("fixtures/meta_and_asset_ctxs.json");
+    const L2_BOOK: &str = include_str!("fixtures/l2_book.json");
+    const USER_FEES: &str = include_str!("fixtures/user_fees.json");
+    const USER_ROLE: &str = include_str!("fixtures/user_role.json");
+    const CANDLE_SNAPSHOT: &str = include_str!("fixtures/candle_snapshot.json");
+    const FUNDING_HISTORY: &str = include_str!("fixtures/funding_history.json");
+    const USER_FUNDING: &str = include_str!("fixtures/user_funding.json");
+    const PREDICTED_FUNDINGS: &str = include_str!("fixtures/predicted_fundings.json");
+    const REFERRAL: &str = include_str!("fixtures/referral.json");
+    const ORDER_STATUS: &str = include_str!("fixtures/order_status.json");
+    const FRONTEND_OPEN_ORDERS: &str = include_str!("fixtures/frontend_open_orders.json");
+    const PORTFOLIO: &str = include_str!("fixtures/portfolio.json");
+    const VAULT_DETAILS: &str = include_str!("fixtures/vault_details.json");
+    const USER_RATE_LIMIT: &str = include_str!("fixtures/user_rate_limit.json");
+    const EXTRA_AGENTS: &str = include_str!("fixtures/extra_agents.json");
+    const PERP_DEXS: &str = include_str!("fixtures/perp_dexs.json");
+    const SPOT_DEPLOY_STATE: &str = include_str!("fixtures/spot_deploy_state.json");
+    const TOKEN_DETAILS: &str = include_str!("fixtures/token_details.json");
+    const DELEGATOR_SUMMARY: &str = include_str!("fixtures/delegator_summary.json");
+    const DELEGATIONS: &str = include_str!("fixtures/delegations.json");
+    const DELEGATOR_REWARDS: &str = include_str!("fixtures/delegator_rewards.json");
+    const DELEGATOR_HISTORY: &str = include_str!("fixtures/delegator_history.json");
+    const VALIDATOR_SUMMARIES: &str = include_str!("fixtures/validator_summaries.json");
+    const USER_VAULT_EQUITIES: &str = include_str!("fixtures/user_vault_equities.json");
+    const USER_TO_MULTI_SIG_SIGNERS: &str = include_str!("fixtures/user_to_multi_sig_signers.json");
+    const MAX_BUILDER_FEE: &str = include_str!("fixtures/max_builder_fee.json");
+    const SPOT_META: &str = r#"{"tokens":[{"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},{"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true}],"universe":[{"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true}]}"#;
+
+    async fn setup_mock_server() -> (InfoClient, mockito::ServerGuard) {
+        let server = mockito::Server::new_async().await;
         let client = InfoClient::new(server.url().to_string());
         (client, server)
     }
 
+    #[test]
+    fn test_fixtures_match_response_types() {
+        fn parses<Q: InfoQuery>(fixture: &str) {
+            if let Err(e) = serde_json::from_str::<Q::Response>(fixture) {
+                panic!("{} does not parse: {e}", std::any::type_name::<Q>());
+            }
+        }
+
+        parses::<queries::ClearinghouseState>(CLEARINGHOUSE_STATE);
+        parses::<queries::BatchClearinghouseStates>(&format!("[{CLEARINGHOUSE_STATE}]"));
+        parses::<queries::SpotClearinghouseState>(SPOT_CLEARINGHOUSE_STATE);
+        parses::<queries::OpenOrders>(OPEN_ORDERS);
+        parses::<queries::HistoricalOrders>(HISTORICAL_ORDERS);
+        parses::<queries::UserFills>(USER_FILLS);
+        parses::<queries::UserFillsByTime>(USER_FILLS);
+        parses::<queries::RecentTrades>(RECENT_TRADES);
+        parses::<queries::AllMids>(ALL_MIDS);
+        parses::<queries::Meta>(META);
+        parses::<queries::MetaAndAssetCtxs>(META_AND_ASSET_CTXS);
+        parses::<queries::L2Book>(L2_BOOK);
+        parses::<queries::UserFees>(USER_FEES);
+        parses::<queries::UserRole>(USER_ROLE);
+        parses::<queries::CandleSnapshot>(CANDLE_SNAPSHOT);
+        parses::<queries::FundingHistory>(FUNDING_HISTORY);
+        parses::<queries::UserFunding>(USER_FUNDING);
+        parses::<queries::PredictedFundings>(PREDICTED_FUNDINGS);
+        parses::<queries::Referral>(REFERRAL);
+        parses::<queries::OrderStatusQuery>(ORDER_STATUS);
+        parses::<queries::FrontendOpenOrders>(FRONTEND_OPEN_ORDERS);
+        parses::<queries::Portfolio>(PORTFOLIO);
+        parses::<queries::VaultDetails>(VAULT_DETAILS);
+        parses::<queries::UserRateLimit>(USER_RATE_LIMIT);
+        parses::<queries::ExtraAgents>(EXTRA_AGENTS);
+        parses::<queries::PerpDexs>(PERP_DEXS);
+        parses::<queries::SpotDeployState>(SPOT_DEPLOY_STATE);
+        parses::<queries::TokenDetails>(TOKEN_DETAILS);
+        parses::<queries::DelegatorSummary>(DELEGATOR_SUMMARY);
+        parses::<queries::Delegations>(DELEGATIONS);
+        parses::<queries::DelegatorRewards>(DELEGATOR_REWARDS);
+        parses::<queries::DelegatorHistory>(DELEGATOR_HISTORY);
+        parses::<queries::ValidatorSummaries>(VALIDATOR_SUMMARIES);
+        parses::<queries::UserVaultEquities>(USER_VAULT_EQUITIES);
+        parses::<queries::UserToMultiSigSigners>(USER_TO_MULTI_SIG_SIGNERS);
+        parses::<queries::MaxBuilderFee>(MAX_BUILDER_FEE);
+    }
+
     #[tokio::test]
     async fn test_user_state() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "clearinghouseState", "user": TEST_ADDRESS.to_lowercase()})))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{"user":"0x0D1d9635D0640821d15e323ac8AdADfA9c111414","marginSummary":{"accountValue":"0","totalMarginUsed":"0","totalNtlPos":"0","totalRawUsd":"0","totalRawUsdCc":"0"},"positions":[]}}"#)
+            .with_body(CLEARINGHOUSE_STATE)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.user_state(address).await;
-        assert!(result.is_ok());
+        let state = client.user_state(address, None).await.unwrap();
+        assert_eq!(state.margin_summary.account_value, "13109.482328");
+        assert_eq!(state.cross_margin_summary.total_ntl_pos, "12698.2");
+        assert_eq!(state.cross_maintenance_margin_used, "634.91");
+        assert_eq!(state.withdrawable, "10569.842328");
+        assert_eq!(state.time, 1754450974231);
+        assert_eq!(state.asset_positions.len(), 2);
+        let eth = &state.asset_positions[0];
+        assert_eq!(eth.type_string, "oneWay");
+        assert_eq!(eth.position.coin, "ETH");
+        assert_eq!(eth.position.leverage.value, 5);
+        assert_eq!(eth.position.cum_funding.since_open, "-3.146427");
+        let atom = &state.asset_positions[1].position;
+        assert_eq!(atom.leverage.type_string, "isolated");
+        assert_eq!(atom.leverage.raw_usd.as_deref(), Some("1220.97"));
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_user_states() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "batchClearinghouseStates", "users": [TEST_ADDRESS.to_lowercase()]})))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[{"user":"0x0D1d9635D0640821d15e323ac8AdADfA9c111414","marginSummary":{"accountValue":"0","totalMarginUsed":"0","totalNtlPos":"0","totalRawUsd":"0","totalRawUsdCc":"0"},"positions":[]}]}"#)
+            .with_body(format!("[{CLEARINGHOUSE_STATE}]"))
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.user_states(vec![address]).await;
-        assert!(result.is_ok());
+        let states = client.user_states(vec![address]).await.unwrap();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].withdrawable, "10569.842328");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_open_orders() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "openOrders", "user": TEST_ADDRESS.to_lowercase()})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(OPEN_ORDERS)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let orders = client.open_orders(address, None).await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].limit_px, "29792.0");
+        assert_eq!(orders[0].orig_sz, "5.0");
+        assert_eq!(orders[0].cloid, None);
+        assert_eq!(orders[1].cloid.as_deref(), Some("0x1e60610f0b3d420597c88c1fed2ad5ee"));
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_user_token_balances() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[{"coin":"ETH","hold":"0","total":"0"}]}"#)
+            .with_body(SPOT_CLEARINGHOUSE_STATE)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.user_token_balances(address).await;
-        assert!(result.is_ok());
+        let balances = client.user_token_balances(address).await.unwrap();
+        assert_eq!(balances.len(), 4);
+        assert_eq!(balances[1].coin, "PURR");
+        assert_eq!(balances[1].token, 1);
+        assert_eq!(balances[1].hold, "0");
+        assert_eq!(balances[1].entry_ntl, "1234.56");
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_user_fees() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{"tier":0,"makerRate":"0","takerRate":"0","totalVolume":"0"}}"#)
+            .with_body(USER_FEES)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.user_fees(address).await;
-        assert!(result.is_ok());
+        let fees = client.user_fees(address).await.unwrap();
+        assert_eq!(fees.user_cross_rate, "0.000432");
+        assert_eq!(fees.active_referral_discount, "0.04");
+        assert_eq!(fees.daily_user_vlm[0].user_cross, "1200.5");
+        assert_eq!(fees.fee_schedule.referral_discount, "0.04");
+        assert_eq!(fees.fee_schedule.tiers.vip[0].ntl_cutoff, "5000000.0");
+        assert_eq!(fees.fee_schedule.tiers.mm[0].maker_fraction_cutoff, "0.005");
+        assert_eq!(fees.fee_schedule.staking_discount_tiers[1].discount, "0.05");
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_account_introspection() {
+        let (client, mut server) = setup_mock_server().await;
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let request = |kind: &str| {
+            mockito::Matcher::Json(serde_json::json!({"type": kind, "user": TEST_ADDRESS.to_lowercase()}))
+        };
+        let role = server.mock("POST", "/info")
+            .match_body(request("userRole"))
+            .with_status(200)
+            .with_body(USER_ROLE)
+            .create();
+        let rate_limit = server.mock("POST", "/info")
+            .match_body(request("userRateLimit"))
+            .with_status(200)
+            .with_body(USER_RATE_LIMIT)
+            .create();
+        let agents = server.mock("POST", "/info")
+            .match_body(request("extraAgents"))
+            .with_status(200)
+            .with_body(EXTRA_AGENTS)
+            .create();
+        let vip = server.mock("POST", "/info")
+            .match_body(request("isVip"))
+            .with_status(200)
+            .with_body("null")
+            .create();
+
+        assert_eq!(
+            client.user_role(address).await.unwrap(),
+            UserRole::Agent { user: Address::from_str("0x677d831aef5328190852e24f13c46cac05f984e7").unwrap() }
+        );
+        assert_eq!(client.user_rate_limit(address).await.unwrap().remaining(), 2864574 - 2890);
+        let extra_agents = client.extra_agents(address).await.unwrap();
+        assert_eq!(extra_agents[0].name, "bot");
+        assert_eq!(extra_agents[0].valid_until, 1750000000000);
+        assert!(!client.is_vip(address).await.unwrap());
+        role.assert();
+        rate_limit.assert();
+        agents.assert();
+        vip.assert();
+
+        let missing: UserRole = serde_json::from_str(r#"{"role":"missing"}"#).unwrap();
+        assert_eq!(missing, UserRole::Missing);
+        let sub_account: UserRole = serde_json::from_str(r#"{"role":"subAccount","data":{"master":"0x677d831aef5328190852e24f13c46cac05f984e7"}}"#).unwrap();
+        assert!(matches!(sub_account, UserRole::SubAccount { .. }));
+    }
+
     #[tokio::test]
     async fn test_meta() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"meta":{"assets":{}}}"#)
+            .with_body(META)
             .create();
 
-        let result = client.get_meta().await;
-        assert!(result.is_ok());
+        let meta = client.get_meta(None).await.unwrap();
+        assert_eq!(meta.universe.len(), 3);
+        assert_eq!(meta.universe[1].sz_decimals, 4);
+        assert_eq!(meta.margin_table(55).unwrap().margin_tiers[1].max_leverage, 15);
+        assert_eq!(meta.collateral_token, Some(0));
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_perp_dex_queries() {
+        let (client, mut server) = setup_mock_server().await;
+        let dexs_mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "perpDexs"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(PERP_DEXS)
+            .create();
+        let meta_mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "meta", "dex": "test"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"universe":[{"name":"test:ABC","szDecimals":2,"maxLeverage":10}]}"#)
+            .create();
+        let mids_mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "allMids", "dex": "test"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"test:ABC":"10.05"}"#)
+            .create();
+
+        let dexs = client.perp_dexs().await.unwrap();
+        assert!(dexs[0].is_none());
+        assert_eq!(dexs[1].as_ref().unwrap().name, "test");
+        let meta = client.get_meta(Some("test".to_string())).await.unwrap();
+        assert_eq!(meta.universe[0].name, "test:ABC");
+        let mids = client.all_mids(Some("test".to_string())).await.unwrap();
+        assert_eq!(mids["test:ABC"], "10.05");
+        dexs_mock.assert();
+        meta_mock.assert();
+        mids_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_spot_meta() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"spotMeta":{"assets":{}}}"#)
+            .with_body(SPOT_META)
             .create();
 
-        let result = client.get_spot_meta().await;
-        assert!(result.is_ok());
+        let spot_meta = client.get_spot_meta().await.unwrap();
+        assert_eq!(spot_meta.tokens.len(), 2);
+        assert_eq!(spot_meta.universe[0].name, "PURR/USDC");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_meta_and_asset_ctxs() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "metaAndAssetCtxs"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(META_AND_ASSET_CTXS)
+            .create();
+
+        let result = client.meta_and_asset_ctxs().await.unwrap();
+        let (btc, btc_ctx) = result.get("BTC").unwrap();
+        assert_eq!(btc.max_leverage, 40);
+        assert_eq!(btc_ctx.funding, "0.0000125");
+        assert_eq!(btc_ctx.shared.mark_px, "107431.0");
+        let tiers = &result.meta.margin_table(56).unwrap().margin_tiers;
+        assert_eq!(tiers[1].max_leverage, 20);
+
+        let (matic, matic_ctx) = result.get("MATIC").unwrap();
+        assert!(matic.is_delisted && matic.only_isolated);
+        assert!(matic_ctx.shared.mid_px.is_none());
+        assert_eq!(result.assets().count(), 2);
+        assert!(result.get("ETH").is_none());
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_spot_meta_and_asset_contexts() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{"meta":{"assets":{}},"contexts":[]}}"#)
+            .with_body(format!(
+                r#"[{SPOT_META},[{{"dayNtlVlm":"8906.0","markPx":"0.14","midPx":"0.209265","prevDayPx":"0.20432","circulatingSupply":"598274922.83822","coin":"PURR/USDC"}}]]"#
+            ))
             .create();
 
-        let result = client.spot_meta_and_asset_contexts().await;
-        assert!(result.is_ok());
+        let result = client.spot_meta_and_asset_contexts().await.unwrap();
+        assert!(matches!(&result[0], SpotMetaAndAssetCtxs::SpotMeta(meta) if meta.universe.len() == 1));
+        assert!(matches!(&result[1], SpotMetaAndAssetCtxs::Context(ctxs) if ctxs[0].coin == "PURR/USDC"));
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_spot_balances_usdc_value() {
+        let (client, mut server) = setup_mock_server().await;
+        server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "spotClearinghouseState"})))
+            .with_body(SPOT_CLEARINGHOUSE_STATE)
+            .create();
+        server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "spotMeta"})))
+            .with_body(SPOT_META)
+            .create();
+        server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "allMids"})))
+            .with_body(r#"{"BTC":"60000.0","PURR/USDC":"0.25"}"#)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let value = client.spot_balances_usdc_value(address).await.unwrap();
+        assert!((value.total - (14.625485 + 2000.0 * 0.25)).abs() < 1e-9);
+        // The zero HFUN balance is skipped; JEFF has no USDC pair
+        assert_eq!(value.unpriced, vec!["JEFF".to_string()]);
+    }
+
     #[tokio::test]
     async fn test_all_mids() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{}}"#)
+            .with_body(ALL_MIDS)
             .create();
 
-        let result = client.all_mids().await;
-        assert!(result.is_ok());
+        let mids = client.all_mids(None).await.unwrap();
+        assert_eq!(mids["ETH"], "3644.85");
+        assert_eq!(mids["@107"], "44.1215");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_custom_query_and_info_raw() {
+        #[derive(Serialize)]
+        #[serde(tag = "type", rename = "userTwapSliceFills")]
+        struct UserTwapSliceFills {
+            user: Address,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct TwapSliceFill {
+            twap_id: u64,
+        }
+
+        impl InfoQuery for UserTwapSliceFills {
+            type Response = Vec<TwapSliceFill>;
+        }
+
+        let (client, mut server) = setup_mock_server().await;
+        let request = serde_json::json!({"type": "userTwapSliceFills", "user": TEST_ADDRESS.to_lowercase()});
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(request.clone()))
+            .with_status(200)
+            .with_body(r#"[{"fill":{"coin":"AVAX"},"twapId":3156}]"#)
+            .expect(2)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let fills = client.query(&UserTwapSliceFills { user: address }).await.unwrap();
+        assert_eq!(fills[0].twap_id, 3156);
+
+        let raw = client.info_raw(request).await.unwrap();
+        assert_eq!(raw[0]["fill"]["coin"], "AVAX");
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_user_fills() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(USER_FILLS)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let fills = client.user_fills(address).await.unwrap();
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].tid, 118906512037719);
+        assert_eq!(fills[0].builder_fee.as_deref(), Some("0.01"));
+        assert_eq!(fills[1].coin, "@107");
+        assert_eq!(fills[1].builder_fee, None);
+        mock.assert();
+    }
+
+    fn fill_json(time: u64, tid: u64) -> serde_json::Value {
+        serde_json::json!({
+            "coin": "AVAX", "px": "18.435", "sz": "93.53", "side": "B", "time": time,
+            "startPosition": "26.86", "dir": "Open Long", "closedPnl": "0.0",
+            "hash": "0xa166e3fa63c25663024b03f2e0da011a00307e4017465df020210d3d432e7cb8",
+            "oid": 90542681, "crossed": false, "fee": "0.01", "tid": tid, "feeToken": "USDC"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_user_fills_by_time() {
+        let (client, mut server) = setup_mock_server().await;
+        let body = serde_json::Value::Array(vec![fill_json(1681222254710, 118906512037719)]);
         let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "type": "userFillsByTime",
+                "user": TEST_ADDRESS.to_lowercase(),
+                "startTime": 1681222254000u64,
+                "aggregateByTime": true
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[]}"#)
+            .with_body(body.to_string())
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.user_fills(address).await;
-        assert!(result.is_ok());
+        let fills = client.user_fills_by_time(address, 1681222254000, None, Some(true)).await.unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].tid, 118906512037719);
+        assert_eq!(fills[0].dir, "Open Long");
+        assert_eq!(fills[0].builder_fee, None);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_fills_stream() {
+        let (client, mut server) = setup_mock_server().await;
+        let first_page: Vec<_> = (1000..1000 + USER_FILLS_PAGE_LIMIT as u64).map(|t| fill_json(t, t)).collect();
+        let last_time = 999 + USER_FILLS_PAGE_LIMIT as u64;
+        let first = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"startTime": 1000, "endTime": 10000})))
+            .with_status(200)
+            .with_body(serde_json::Value::Array(first_page).to_string())
+            .create();
+        let second_page = vec![fill_json(last_time, last_time), fill_json(last_time + 1, last_time + 1)];
+        let second = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"startTime": last_time})))
+            .with_status(200)
+            .with_body(serde_json::Value::Array(second_page).to_string())
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let fills: Vec<Fill> = client.fills_stream(address, 1000..=10000).try_collect().await.unwrap();
+        assert_eq!(fills.len(), USER_FILLS_PAGE_LIMIT + 1);
+        assert!(fills.windows(2).all(|w| w[0].tid < w[1].tid));
+        assert_eq!(fills.last().unwrap().tid, last_time + 1);
+        first.assert();
+        second.assert();
+    }
+
+    #[tokio::test]
+    async fn test_fills_stream_full_millisecond() {
+        let (client, mut server) = setup_mock_server().await;
+        let page: Vec<_> = (0..USER_FILLS_PAGE_LIMIT as u64).map(|tid| fill_json(1000, tid)).collect();
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"startTime": 1000})))
+            .with_status(200)
+            .with_body(serde_json::Value::Array(page).to_string())
+            .expect(1)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let results: Vec<Result<Fill>> = client.fills_stream(address, 1000..=10000).collect().await;
+        assert_eq!(results.len(), USER_FILLS_PAGE_LIMIT + 1);
+        assert!(results[..USER_FILLS_PAGE_LIMIT].iter().all(|fill| fill.is_ok()));
+        assert!(matches!(
+            results.last().unwrap(),
+            Err(HyperliquidError::InvalidResponse(message)) if message.contains("at 1000 ms")
+        ));
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_funding_history() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[]}"#)
+            .with_body(FUNDING_HISTORY)
             .create();
 
         let result = client.funding_history("ETH".to_string(), 1690393044548, Some(1690393044548 + 3600)).await;
-        assert!(result.is_ok());
+        let history = result.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].funding_rate, "-0.00022196");
+        assert_eq!(history[0].premium, "-0.00052196");
+        assert_eq!(history[0].time, 1683849600076);
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_user_funding_history() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[]}"#)
+            .with_body(USER_FUNDING)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
         let result = client.user_funding_history(address, 1690393044548, Some(1690393044548 + 3600)).await;
-        assert!(result.is_ok());
+        let fundings = result.unwrap();
+        assert_eq!(fundings.len(), 2);
+        assert_eq!(fundings[0].time, 1681222254710);
+        assert_eq!(fundings[0].delta.usdc, "-3.625312");
+        assert_eq!(fundings[0].delta.n_samples, None);
+        assert_eq!(fundings[1].delta.n_samples, Some(24));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_user_funding_stream() {
+        let (client, mut server) = setup_mock_server().await;
+        let funding = |time: u64, coin: &str| {
+            serde_json::json!({
+                "delta": {"coin": coin, "fundingRate": "0.0000125", "szi": "1.0", "type": "funding", "usdc": "-1.0", "nSamples": 24},
+                "hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "time": time
+            })
+        };
+        let last_time = FUNDING_PAGE_LIMIT as u64 / 2;
+        let first_page: Vec<_> = (0..FUNDING_PAGE_LIMIT as u64)
+            .map(|i| funding(i / 2 + 1, if i % 2 == 0 { "BTC" } else { "ETH" }))
+            .collect();
+        let first = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "userFunding", "startTime": 1})))
+            .with_status(200)
+            .with_body(serde_json::Value::Array(first_page).to_string())
+            .create();
+        let second_page = vec![funding(last_time, "BTC"), funding(last_time, "ETH"), funding(last_time, "SOL")];
+        let second = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"startTime": last_time})))
+            .with_status(200)
+            .with_body(serde_json::Value::Array(second_page).to_string())
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let fundings: Vec<UserFundingResponse> =
+            client.user_funding_stream(address, 1..=last_time).try_collect().await.unwrap();
+        assert_eq!(fundings.len(), FUNDING_PAGE_LIMIT + 1);
+        assert_eq!(fundings.last().unwrap().delta.coin, "SOL");
+        assert_eq!(fundings[0].delta.n_samples, Some(24));
+        first.assert();
+        second.assert();
+    }
+
+    #[tokio::test]
+    async fn test_predicted_fundings() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "predictedFundings"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(PREDICTED_FUNDINGS)
+            .create();
+
+        let predicted = client.predicted_fundings().await.unwrap();
+        let avax = &predicted["AVAX"];
+        assert_eq!(avax.len(), 2);
+        assert_eq!(avax["HlPerp"].funding_rate, "0.0000125");
+        assert_eq!(avax["HlPerp"].funding_interval_hours, Some(1));
+        assert_eq!(avax["BinPerp"].next_funding_time, 1733961600000);
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_recent_trades() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[]}"#)
+            .with_body(RECENT_TRADES)
             .create();
 
-        let result = client.recent_trades("ETH".to_string()).await;
-        assert!(result.is_ok());
+        let trades = client.recent_trades("ETH".to_string()).await.unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].tid, 852150371390733);
+        assert_eq!(trades[0].users[0], Address::from_str(TEST_ADDRESS).unwrap());
+        assert_eq!(trades[1].side, "A");
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_l2_snapshot() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{"levels":[]}}"#)
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "l2Book", "coin": "ETH"})))
+            .with_body(L2_BOOK)
+            .create();
+
+        let book = client.l2_snapshot("ETH".to_string(), L2BookOptions::default()).await.unwrap();
+        assert_eq!(book.time, 1754450974231);
+        assert_eq!(book.bids().len(), 2);
+        assert_eq!(book.bids()[0].px, "3644.8");
+        assert_eq!(book.asks()[0].n, 4);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_l2_snapshot_aggregated() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "l2Book", "coin": "BTC", "nSigFigs": 5, "mantissa": 2})))
+            .with_status(200)
+            .with_body(r#"{"coin":"BTC","time":1754450974231,"levels":[[{"px":"114500","sz":"12.5","n":40}],[{"px":"114520","sz":"8.1","n":22}]]}"#)
             .create();
 
-        let result = client.l2_snapshot("ETH".to_string()).await;
-        assert!(result.is_ok());
+        let options = L2BookOptions::sig_figs(5).with_mantissa(2);
+        let book = client.l2_snapshot("BTC".to_string(), options).await.unwrap();
+        assert_eq!(book.asks()[0].px, "114520");
         mock.assert();
+
+        assert!(client.l2_snapshot("BTC".to_string(), L2BookOptions::sig_figs(6)).await.is_err());
+        assert!(client.l2_snapshot("BTC".to_string(), L2BookOptions::sig_figs(3).with_mantissa(2)).await.is_err());
+
+        let subscription = Subscription::L2Book { coin: "BTC".to_string(), options: L2BookOptions::sig_figs(3) };
+        assert_eq!(
+            serde_json::to_value(&subscription).unwrap(),
+            serde_json::json!({"type": "l2Book", "coin": "BTC", "nSigFigs": 3})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_l2_book_invalid_options() {
+        let (mut client, _server) = setup_mock_server().await;
+        let (sender, _receiver) = tokio::sync::mpsc::unbounded_channel();
+
+        for options in [L2BookOptions::sig_figs(7), L2BookOptions::sig_figs(3).with_mantissa(2)] {
+            let subscription = Subscription::L2Book { coin: "BTC".to_string(), options };
+            assert!(client.subscribe(subscription, sender.clone()).await.is_err());
+        }
+        assert!(client.ws_manager.is_none());
     }
 
     #[tokio::test]
     async fn test_candles_snapshot() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[]}"#)
+            .with_body(CANDLE_SNAPSHOT)
             .create();
 
-        let result = client.candles_snapshot("ETH".to_string(), "1m".to_string(), 1690393044548, 1690393044548 + 3600).await;
-        assert!(result.is_ok());
+        let result = client.candles_snapshot("ETH".to_string(), CandleInterval::OneMinute, 1690393044548, 1690393044548 + 3600).await;
+        let candles = result.unwrap();
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].time_open, 1690393020000);
+        assert_eq!(candles[0].close_f64().unwrap(), 1871.5);
+        assert_eq!(candles[0].volume_f64().unwrap(), 12.5);
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_candles_snapshot_chunks_long_ranges() {
+        let (client, mut server) = setup_mock_server().await;
+        let candle = |time_open: u64| {
+            serde_json::json!({
+                "T": time_open + 3_599_999, "c": "1.0", "h": "1.0", "i": "1h", "l": "1.0", "n": 1,
+                "o": "1.0", "s": "ETH", "t": time_open, "v": "1.0"
+            })
+        };
+        let chunk_ms = 3_600_000 * CANDLE_LIMIT;
+        let first = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "type": "candleSnapshot",
+                "req": {"interval": "1h", "startTime": 0, "endTime": chunk_ms - 1}
+            })))
+            .with_status(200)
+            .with_body(serde_json::json!([candle(0), candle(chunk_ms - 3_600_000)]).to_string())
+            .create();
+        let second = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "req": {"startTime": chunk_ms, "endTime": chunk_ms + 3_600_000}
+            })))
+            .with_status(200)
+            .with_body(serde_json::json!([candle(chunk_ms - 3_600_000), candle(chunk_ms)]).to_string())
+            .create();
+
+        let candles = client
+            .candles_snapshot("ETH".to_string(), "1h".parse().unwrap(), 0, chunk_ms + 3_600_000)
+            .await
+            .unwrap();
+        let opens: Vec<u64> = candles.iter().map(|candle| candle.time_open).collect();
+        assert_eq!(opens, vec![0, chunk_ms - 3_600_000, chunk_ms]);
+        first.assert();
+        second.assert();
+    }
+
     #[tokio::test]
     async fn test_query_order_by_oid() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{"order":{"coin":"ETH","side":"B","limitPx":"1000","sz":"1","oid":1,"timestamp":1690393044548,"triggerCondition":"","isTrigger":false,"triggerPx":"0","isPositionTpsl":false,"reduceOnly":false,"orderType":"L","origSz":"1","tif":"Gtc","cloid":null},"status":"open","statusTimestamp":1690393044548}}"#)
+            .with_body(ORDER_STATUS)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
         let result = client.query_order_by_oid(address, 1).await;
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap().status, OrderStatus::Open);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_order_status_by_cloid() {
+        let (client, mut server) = setup_mock_server().await;
+        let cloid = Uuid::from_str("1e60610f-0b3d-4205-97c8-8c1fed2ad5ee").unwrap();
+        let filled = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "type": "orderStatus",
+                "user": TEST_ADDRESS.to_lowercase(),
+                "oid": "0x1e60610f0b3d420597c88c1fed2ad5ee"
+            })))
+            .with_status(200)
+            .with_body(r#"{"status":"order","order":{"order":{"coin":"ETH","side":"A","limitPx":"2000.0","sz":"0.0","oid":7,"timestamp":1690393044548,"triggerCondition":"N/A","isTrigger":false,"triggerPx":"0.0","isPositionTpsl":false,"reduceOnly":false,"orderType":"Limit","origSz":"0.5","tif":"Alo","cloid":"0x1e60610f0b3d420597c88c1fed2ad5ee","children":[]},"status":"perpMarginRejected","statusTimestamp":1690393044549}}"#)
+            .create();
+        let unknown = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"oid": 8})))
+            .with_status(200)
+            .with_body(r#"{"status":"unknownOid"}"#)
+            .expect(2)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let order = client.order_status(address, OrderRef::Cloid(cloid)).await.unwrap().unwrap();
+        assert_eq!(order.order.oid, 7);
+        assert_eq!(order.status, OrderStatus::PerpMarginRejected);
+        assert!(order.status.is_rejected());
+        assert!(client.order_status(address, OrderRef::Oid(8)).await.unwrap().is_none());
+        assert!(client.query_order_by_oid(address, 8).await.is_err());
+        filled.assert();
+        unknown.assert();
+
+        let status: OrderStatus = serde_json::from_str(r#""someNewCanceled""#).unwrap();
+        assert_eq!(status, OrderStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn test_frontend_open_orders() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "frontendOpenOrders", "user": TEST_ADDRESS.to_lowercase()})))
+            .with_status(200)
+            .with_body(FRONTEND_OPEN_ORDERS)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let orders = client.frontend_open_orders(address, None).await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].tif.as_deref(), Some("Gtc"));
+        let stop = &orders[0].children[0];
+        assert!(stop.is_trigger && stop.reduce_only);
+        assert_eq!(stop.order_type, "Stop Market");
+        assert_eq!(stop.trigger_condition, "Price above 30500");
+        assert_eq!(stop.tif, None);
         mock.assert();
     }
 
     #[tokio::test]
     async fn test_query_referral_state() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":{"stage":"none","data":{"required":"0"}}}"#)
+            .with_body(REFERRAL)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.query_referral_state(address).await;
-        assert!(result.is_ok());
+        let result = client.query_referral_state(address).await.unwrap();
+        assert_eq!(result.referred_by.unwrap().code, "TESTNET");
+        assert_eq!(result.unclaimed_rewards, "11.047361");
+        match result.referrer_state {
+            ReferrerState::Ready(data) => {
+                assert_eq!(data.code, "TEST");
+                assert_eq!(data.referral_states[0].time_joined, 1679425029416);
+            }
+            state => panic!("unexpected referrer state {state:?}"),
+        }
         mock.assert();
+
+        let state: ReferrerState =
+            serde_json::from_str(r#"{"stage":"needToTrade","data":{"required":"10000"}}"#).unwrap();
+        assert!(matches!(state, ReferrerState::NeedToTrade { required } if required == "10000"));
+        let state: ReferrerState = serde_json::from_str(r#"{"stage":"needToCreateCode"}"#).unwrap();
+        assert!(matches!(state, ReferrerState::NeedToCreateCode));
     }
 
     #[tokio::test]
     async fn test_historical_orders() {
-        let (client, mut server) = setup_mock_server();
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"data":[]}"#)
+            .with_body(HISTORICAL_ORDERS)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.historical_orders(address).await;
-        assert!(result.is_ok());
+        let orders = client.historical_orders(address).await.unwrap();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].status, OrderStatus::Filled);
+        assert_eq!(orders[0].order.tif.as_deref(), Some("FrontendMarket"));
+        assert!(orders[1].order.is_trigger);
+        assert!(orders[1].status.is_canceled());
         mock.assert();
     }
 
     #[tokio::test]
-    async fn test_staking_info() {
-        let (client, mut server) = setup_mock_server();
+    async fn test_max_builder_fee() {
+        let (client, mut server) = setup_mock_server().await;
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let builder = Address::from_str("0x1ab189b7801140900c711e458212f9c76f8dac79").unwrap();
         let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "type": "maxBuilderFee",
+                "user": address,
+                "builder": builder,
+            })))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"stakingInfo":{"totalStaked":"0","totalRewards":"0","apr":"0","minStake":"0","maxStake":"0","cooldownPeriod":0}}"#)
+            .with_body(MAX_BUILDER_FEE)
             .create();
 
-        let result = client.get_staking_info().await;
-        assert!(result.is_ok());
+        let result = client.max_builder_fee(address, builder).await;
+        assert_eq!(result.unwrap(), 10);
         mock.assert();
     }
 
     #[tokio::test]
-    async fn test_user_staking_info() {
-        let (client, mut server) = setup_mock_server();
+    async fn test_spot_deploy_state() {
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "spotDeployState"})))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"userStakingInfo":{"stakedAmount":"0","pendingRewards":"0","cooldownEnd":null,"unstakeAmount":null}}"#)
+            .with_body(SPOT_DEPLOY_STATE)
             .create();
 
         let address = Address::from_str(TEST_ADDRESS).unwrap();
-        let result = client.get_user_staking_info(address).await;
-        assert!(result.is_ok());
+        let state = client.spot_deploy_state(address).await.unwrap();
+        assert_eq!(state.states[0].token, 1026);
+        assert_eq!(state.states[0].spec.name, "TEST0");
+        assert_eq!(state.states[0].max_supply.as_deref(), Some("1000000000"));
+        assert_eq!(state.states[0].user_genesis_balances[0].0, address);
+        assert_eq!(state.gas_auction.current_gas, None);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_token_details() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "type": "tokenDetails",
+                "tokenId": "0x0d01dc56dcaaca66ad901c959b4011ec",
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(TOKEN_DETAILS)
+            .create();
+
+        let token_id = U128::from_str("0x0d01dc56dcaaca66ad901c959b4011ec").unwrap();
+        let details = client.token_details(token_id).await.unwrap();
+        assert_eq!(details.name, "HYPE");
+        assert_eq!(details.sz_decimals, 2);
+        assert_eq!(details.genesis.unwrap().existing_token_balances[0].0, 1);
+        assert!(details.deployer.is_none());
         mock.assert();
     }
 
     #[tokio::test]
-    async fn test_staking_rewards_info() {
-        let (client, mut server) = setup_mock_server();
+    async fn test_delegator_summary() {
+        let (client, mut server) = setup_mock_server().await;
         let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "delegatorSummary"})))
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"stakingRewardsInfo":{"totalDistributed":"0","currentEpoch":0,"rewardsPerEpoch":"0","epochDuration":0}}"#)
+            .with_body(DELEGATOR_SUMMARY)
             .create();
 
-        let result = client.get_staking_rewards_info().await;
-        assert!(result.is_ok());
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let summary = client.delegator_summary(address).await.unwrap();
+        assert_eq!(summary.delegated, "12060.16529862");
+        assert_eq!(summary.n_pending_withdrawals, 0);
         mock.assert();
     }
+
+    #[tokio::test]
+    async fn test_delegations() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "delegations"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(DELEGATIONS)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let delegations = client.delegations(address).await.unwrap();
+        assert_eq!(delegations[0].amount_wei().unwrap(), 1_206_016_529_862);
+        assert!(delegations[0].is_locked(1735466781352));
+        assert!(!delegations[0].is_locked(1735466781353));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_delegator_rewards_and_history() {
+        let (client, mut server) = setup_mock_server().await;
+        let rewards_mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "delegatorRewards"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(DELEGATOR_REWARDS)
+            .create();
+        let history_mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "delegatorHistory"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(DELEGATOR_HISTORY)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let rewards = client.delegator_rewards(address).await.unwrap();
+        assert_eq!(rewards[1].source, "commission");
+        let history = client.delegator_history(address).await.unwrap();
+        assert!(matches!(
+            &history[0].delta,
+            DelegatorDelta::Delegate { amount, is_undelegate: false, .. } if amount == "10000.0"
+        ));
+        let deposit: DelegatorDelta = serde_json::from_str(r#"{"cDeposit":{"amount":"10000.0"}}"#).unwrap();
+        assert!(matches!(deposit, DelegatorDelta::CDeposit { .. }));
+        rewards_mock.assert();
+        history_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_validator_summaries() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "validatorSummaries"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(VALIDATOR_SUMMARIES)
+            .create();
+
+        let validators = client.validator_summaries().await.unwrap();
+        assert_eq!(validators[0].name, "Hypurr Validator");
+        assert_eq!(validators[0].stats[0].0, "day");
+        assert_eq!(validators[0].stats[0].1.predicted_apr, "0.0238");
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_vault_details() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "type": "vaultDetails",
+                "vaultAddress": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+                "user": "0x0d1d9635d0640821d15e323ac8adadfa9c111414"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(VAULT_DETAILS)
+            .create();
+
+        let vault = Address::from_str("0xdfc24b077bc1425ad1dea75bcb6f8158e10df303").unwrap();
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let details = client.vault_details(vault, Some(address)).await.unwrap();
+        assert_eq!(details.portfolio[0].1.pnl_history[1], (1734483926634, 81724.33));
+        let follower_state = details.follower_state.unwrap();
+        assert!(follower_state.is_locked(1734570326633));
+        assert_eq!(details.followers[0].user, "Leader");
+        assert!(matches!(
+            &details.relationship,
+            VaultRelationship::Parent { child_addresses } if child_addresses.len() == 1
+        ));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_portfolio() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::Json(serde_json::json!({"type": "portfolio", "user": TEST_ADDRESS.to_lowercase()})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(PORTFOLIO)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let portfolio = client.portfolio(address).await.unwrap();
+        // The account was empty at the start of allTime, leaving a single return
+        assert!(portfolio.get(PortfolioPeriod::AllTime).unwrap().stats().is_none());
+
+        let day = portfolio.get(PortfolioPeriod::Day).unwrap();
+        assert_eq!(day.account_value_history[2], (1734483926634, 12631.40));
+        let returns = day.returns();
+        let (first, second) = (-11.44 / 12510.21, (121.19 + 11.44) / 12498.77);
+        assert_eq!(returns.len(), 2);
+        assert_eq!(returns[0].0, 1734440726634);
+        assert!((returns[0].1 - first).abs() < 1e-12);
+        assert!((returns[1].1 - second).abs() < 1e-12);
+
+        let stats = day.stats().unwrap();
+        assert!((stats.total_return - ((1.0 + first) * (1.0 + second) - 1.0)).abs() < 1e-12);
+        assert!((stats.max_drawdown + first).abs() < 1e-12);
+        assert!(stats.volatility > 0.0);
+        assert!((stats.sharpe_ratio - stats.mean_return / stats.volatility).abs() < 1e-12);
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_user_vault_equities() {
+        let (client, mut server) = setup_mock_server().await;
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "userVaultEquities"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(USER_VAULT_EQUITIES)
+            .create();
+
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let equities = client.user_vault_equities(address).await.unwrap();
+        assert_eq!(equities[0].equity, "742500.082809");
+        assert!(!equities[0].is_locked(1734570326634));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_user_to_multi_sig_signers() {
+        let (client, mut server) = setup_mock_server().await;
+        let address = Address::from_str(TEST_ADDRESS).unwrap();
+        let mock = server.mock("POST", "/info")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({"type": "userToMultiSigSigners"})))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(USER_TO_MULTI_SIG_SIGNERS)
+            .expect(1)
+            .create();
+
+        let signers = client.user_to_multi_sig_signers(address).await.unwrap().unwrap();
+        assert_eq!(signers.threshold, 2);
+        assert_eq!(signers.authorized_users[0], address);
+        mock.assert();
+        mock.remove();
+
+        server.mock("POST", "/info")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body("null")
+            .create();
+        assert!(client.user_to_multi_sig_signers(address).await.unwrap().is_none());
+    }
 }
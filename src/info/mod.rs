@@ -1,18 +1,21 @@
 mod types;
 mod info_client;
 mod response_structs;
+pub mod queries;
 pub mod sub_structs;
 
 pub use response_structs::{
-    UserStateResponse, UserTokenBalanceResponse, UserFeesResponse,
-    UserStatesResponse, UserTokenBalancesResponse, FundingHistoryResponse,
-    SpotMetaAndAssetContextsResponse, CandlesSnapshotResponse, OrderStatusResponse,
-    UserFundingResponse, ReferralResponse, L2SnapshotResponse, RecentTradesResponse,
-    OpenOrdersResponse, UserFillsResponse,
+    UserTokenBalanceResponse, UserFeesResponse, OrderStatusResponse,
+    UserFundingResponse, ReferralResponse, L2SnapshotResponse,
 };
 pub use sub_structs::{
     UserTokenBalance, ReferrerState, ReferrerData, Position, UserState, Level,
-    OrderInfo, Delta, AssetPosition, BasicOrderInfo,
+    OrderInfo, Delta, AssetPosition, BasicOrderInfo, SpotDeployState, SpotDeployTokenState,
+    GasAuction, TokenDetails, TokenGenesis, DelegatorSummary, Delegation, DelegatorReward,
+    DelegatorHistoryEntry, DelegatorDelta, ValidatorSummary, ValidatorStats, VaultDetails,
+    PortfolioHistory, VaultFollower, VaultRelationship, UserVaultEquity, Referrer, ReferredUser,
+    PredictedFunding, Portfolio, PortfolioPeriod, PortfolioStats, DailyUserVlm, FeeSchedule,
+    Tiers, Mm, Vip, StakingDiscount, UserRole, UserRateLimit, ExtraAgent, OpenOrder,
 };
 pub use info_client::InfoClient;
-pub use types::UserTokenBalance as TypeUserTokenBalance;
+pub use queries::{InfoQuery, OrderRef};
@@ -0,0 +1,488 @@
+//! Requests to the `/info` endpoint. Each query serializes to the request body, `type`
+//! included, and names the response it deserializes into through [`InfoQuery`], so queries the
+//! SDK doesn't model yet can be defined downstream and sent with [`InfoClient::query`].
+//!
+//! [`InfoClient::query`]: crate::InfoClient::query
+
+use crate::{
+    exchange::MultiSigSigners,
+    helpers::uuid_to_hex_string,
+    info::{
+        response_structs::{
+            L2SnapshotResponse, OrderStatusResponse, ReferralResponse, UserFeesResponse,
+            UserFundingResponse, UserTokenBalanceResponse,
+        },
+        sub_structs::{
+            self, BasicOrderInfo, Delegation, DelegatorHistoryEntry, DelegatorReward, ExtraAgent,
+            OpenOrder, OrderInfo, PredictedFunding, UserState, UserVaultEquity, ValidatorSummary,
+        },
+        types::{Fill, FundingRate, Trade},
+    },
+    meta::{self, PerpDex},
+    ws::{CandleData, CandleInterval, L2BookOptions},
+};
+use alloy_primitives::Address;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A request to the `/info` endpoint and the type of its response.
+pub trait InfoQuery: Serialize {
+    type Response: DeserializeOwned;
+}
+
+/// Any request body, answered with the untyped response.
+impl InfoQuery for serde_json::Value {
+    type Response = serde_json::Value;
+}
+
+/// `predictedFundings` as sent: each coin with a `[venue, prediction]` pair per venue.
+pub type PredictedFundingsResponse = Vec<(String, Vec<(String, Option<PredictedFunding>)>)>;
+
+/// An order looked up by its exchange-assigned id or by the client order id it was placed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderRef {
+    Oid(u64),
+    Cloid(Uuid),
+}
+
+impl Serialize for OrderRef {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            OrderRef::Oid(oid) => serializer.serialize_u64(*oid),
+            OrderRef::Cloid(cloid) => serializer.serialize_str(&uuid_to_hex_string(*cloid)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderRef {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Oid(u64),
+            Cloid(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Oid(oid) => Ok(OrderRef::Oid(oid)),
+            Raw::Cloid(cloid) => Uuid::parse_str(cloid.trim_start_matches("0x"))
+                .map(OrderRef::Cloid)
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CandleSnapshotRequest {
+    pub coin: String,
+    pub interval: CandleInterval,
+    pub start_time: u64,
+    pub end_time: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "clearinghouseState")]
+pub struct ClearinghouseState {
+    pub user: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+}
+
+impl InfoQuery for ClearinghouseState {
+    type Response = UserState;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "batchClearinghouseStates")]
+pub struct BatchClearinghouseStates {
+    pub users: Vec<Address>,
+}
+
+impl InfoQuery for BatchClearinghouseStates {
+    type Response = Vec<UserState>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "spotClearinghouseState")]
+pub struct SpotClearinghouseState {
+    pub user: Address,
+}
+
+impl InfoQuery for SpotClearinghouseState {
+    type Response = UserTokenBalanceResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userFees")]
+pub struct UserFees {
+    pub user: Address,
+}
+
+impl InfoQuery for UserFees {
+    type Response = UserFeesResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userRole")]
+pub struct UserRole {
+    pub user: Address,
+}
+
+impl InfoQuery for UserRole {
+    type Response = sub_structs::UserRole;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userRateLimit")]
+pub struct UserRateLimit {
+    pub user: Address,
+}
+
+impl InfoQuery for UserRateLimit {
+    type Response = sub_structs::UserRateLimit;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "extraAgents")]
+pub struct ExtraAgents {
+    pub user: Address,
+}
+
+impl InfoQuery for ExtraAgents {
+    type Response = Vec<ExtraAgent>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "isVip")]
+pub struct IsVip {
+    pub user: Address,
+}
+
+impl InfoQuery for IsVip {
+    type Response = Option<bool>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "openOrders")]
+pub struct OpenOrders {
+    pub user: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+}
+
+impl InfoQuery for OpenOrders {
+    type Response = Vec<OpenOrder>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "orderStatus")]
+pub struct OrderStatusQuery {
+    pub user: Address,
+    pub oid: OrderRef,
+}
+
+impl InfoQuery for OrderStatusQuery {
+    type Response = OrderStatusResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "frontendOpenOrders")]
+pub struct FrontendOpenOrders {
+    pub user: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+}
+
+impl InfoQuery for FrontendOpenOrders {
+    type Response = Vec<BasicOrderInfo>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "meta")]
+pub struct Meta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+}
+
+impl InfoQuery for Meta {
+    type Response = meta::Meta;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "spotMeta")]
+pub struct SpotMeta {}
+
+impl InfoQuery for SpotMeta {
+    type Response = meta::SpotMeta;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "spotMetaAndAssetCtxs")]
+pub struct SpotMetaAndAssetCtxs {}
+
+impl InfoQuery for SpotMetaAndAssetCtxs {
+    type Response = Vec<meta::SpotMetaAndAssetCtxs>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "metaAndAssetCtxs")]
+pub struct MetaAndAssetCtxs {}
+
+impl InfoQuery for MetaAndAssetCtxs {
+    type Response = meta::MetaAndAssetCtxs;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "allMids")]
+pub struct AllMids {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dex: Option<String>,
+}
+
+impl InfoQuery for AllMids {
+    type Response = HashMap<String, String>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "perpDexs")]
+pub struct PerpDexs {}
+
+impl InfoQuery for PerpDexs {
+    type Response = Vec<Option<PerpDex>>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userFills")]
+pub struct UserFills {
+    pub user: Address,
+}
+
+impl InfoQuery for UserFills {
+    type Response = Vec<Fill>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userFillsByTime", rename_all = "camelCase")]
+pub struct UserFillsByTime {
+    pub user: Address,
+    pub start_time: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregate_by_time: Option<bool>,
+}
+
+impl InfoQuery for UserFillsByTime {
+    type Response = Vec<Fill>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "fundingHistory", rename_all = "camelCase")]
+pub struct FundingHistory {
+    pub coin: String,
+    pub start_time: u64,
+    pub end_time: Option<u64>,
+}
+
+impl InfoQuery for FundingHistory {
+    type Response = Vec<FundingRate>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userFunding", rename_all = "camelCase")]
+pub struct UserFunding {
+    pub user: Address,
+    pub start_time: u64,
+    pub end_time: Option<u64>,
+}
+
+impl InfoQuery for UserFunding {
+    type Response = Vec<UserFundingResponse>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "predictedFundings")]
+pub struct PredictedFundings {}
+
+impl InfoQuery for PredictedFundings {
+    type Response = PredictedFundingsResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "l2Book")]
+pub struct L2Book {
+    pub coin: String,
+    #[serde(flatten)]
+    pub options: L2BookOptions,
+}
+
+impl InfoQuery for L2Book {
+    type Response = L2SnapshotResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "recentTrades")]
+pub struct RecentTrades {
+    pub coin: String,
+}
+
+impl InfoQuery for RecentTrades {
+    type Response = Vec<Trade>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "candleSnapshot")]
+pub struct CandleSnapshot {
+    pub req: CandleSnapshotRequest,
+}
+
+impl InfoQuery for CandleSnapshot {
+    type Response = Vec<CandleData>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "referral")]
+pub struct Referral {
+    pub user: Address,
+}
+
+impl InfoQuery for Referral {
+    type Response = ReferralResponse;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "historicalOrders")]
+pub struct HistoricalOrders {
+    pub user: Address,
+}
+
+impl InfoQuery for HistoricalOrders {
+    type Response = Vec<OrderInfo>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "delegatorSummary")]
+pub struct DelegatorSummary {
+    pub user: Address,
+}
+
+impl InfoQuery for DelegatorSummary {
+    type Response = sub_structs::DelegatorSummary;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "delegations")]
+pub struct Delegations {
+    pub user: Address,
+}
+
+impl InfoQuery for Delegations {
+    type Response = Vec<Delegation>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "delegatorRewards")]
+pub struct DelegatorRewards {
+    pub user: Address,
+}
+
+impl InfoQuery for DelegatorRewards {
+    type Response = Vec<DelegatorReward>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "delegatorHistory")]
+pub struct DelegatorHistory {
+    pub user: Address,
+}
+
+impl InfoQuery for DelegatorHistory {
+    type Response = Vec<DelegatorHistoryEntry>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "validatorSummaries")]
+pub struct ValidatorSummaries {}
+
+impl InfoQuery for ValidatorSummaries {
+    type Response = Vec<ValidatorSummary>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "maxBuilderFee")]
+pub struct MaxBuilderFee {
+    pub user: Address,
+    pub builder: Address,
+}
+
+impl InfoQuery for MaxBuilderFee {
+    type Response = u64;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "spotDeployState")]
+pub struct SpotDeployState {
+    pub user: Address,
+}
+
+impl InfoQuery for SpotDeployState {
+    type Response = sub_structs::SpotDeployState;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "tokenDetails", rename_all = "camelCase")]
+pub struct TokenDetails {
+    pub token_id: String,
+}
+
+impl InfoQuery for TokenDetails {
+    type Response = sub_structs::TokenDetails;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "vaultDetails", rename_all = "camelCase")]
+pub struct VaultDetails {
+    pub vault_address: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<Address>,
+}
+
+impl InfoQuery for VaultDetails {
+    type Response = sub_structs::VaultDetails;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userVaultEquities")]
+pub struct UserVaultEquities {
+    pub user: Address,
+}
+
+impl InfoQuery for UserVaultEquities {
+    type Response = Vec<UserVaultEquity>;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "portfolio")]
+pub struct Portfolio {
+    pub user: Address,
+}
+
+impl InfoQuery for Portfolio {
+    type Response = sub_structs::Portfolio;
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", rename = "userToMultiSigSigners")]
+pub struct UserToMultiSigSigners {
+    pub user: Address,
+}
+
+impl InfoQuery for UserToMultiSigSigners {
+    type Response = Option<MultiSigSigners>;
+}
@@ -1,127 +1,79 @@
 use crate::{
     info::{
-        sub_structs::{AssetPosition, Level, MarginSummary, DailyUserVlm, Delta, FeeSchedule, OrderInfo, Referrer, ReferrerState, UserState},
-        types::{UserFee, SpotMetaAndAssetContexts, UserTokenBalance, Candle, Fill, Trade, FundingRate, StakingInfo, UserStakingInfo, StakingRewardsInfo},
+        sub_structs::{Level, DailyUserVlm, Delta, FeeSchedule, OrderInfo, Referrer, ReferrerState, StakingDiscount},
+        sub_structs::UserTokenBalance,
     },
-    meta::{SpotMeta, Meta},
 };
 use serde::Deserialize;
 
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct UserStateResponse {
-    pub data: UserState,
-}
-
 #[derive(Deserialize, Debug)]
 pub struct UserTokenBalanceResponse {
-    pub data: Vec<UserTokenBalance>,
+    pub balances: Vec<UserTokenBalance>,
 }
 
-#[derive(Deserialize, Debug)]
+/// A user's fee rates from `userFees`, with the schedule and discounts they derive from.
+#[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UserFeesResponse {
-    pub data: UserFee,
-}
-
-#[derive(Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct UserStatesResponse {
-    pub data: Vec<UserState>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct UserTokenBalancesResponse {
-    pub data: Vec<UserTokenBalance>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct FundingHistoryResponse {
-    pub data: Vec<FundingRate>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct SpotMetaAndAssetContextsResponse {
-    pub data: SpotMetaAndAssetContexts,
-}
-
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct OpenOrdersResponse {
-    pub data: Vec<OrderInfo>,
-}
-
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct UserFillsResponse {
-    pub data: Vec<Fill>,
-}
-
-#[derive(serde::Deserialize, Debug)]
-pub struct CandlesSnapshotResponse {
-    pub data: Vec<Candle>,
-}
-
-#[derive(Deserialize, Debug)]
-pub struct OrderStatusResponse {
-    pub data: OrderInfo,
-}
-
+    pub daily_user_vlm: Vec<DailyUserVlm>,
+    pub fee_schedule: FeeSchedule,
+    pub user_cross_rate: String,
+    pub user_add_rate: String,
+    #[serde(default)]
+    pub user_spot_cross_rate: Option<String>,
+    #[serde(default)]
+    pub user_spot_add_rate: Option<String>,
+    /// Discount the user gets from having been referred.
+    pub active_referral_discount: String,
+    #[serde(default)]
+    pub active_staking_discount: Option<StakingDiscount>,
+}
+
+/// An `orderStatus` lookup: the order, or `UnknownOid` when the user has no such order.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum OrderStatusResponse {
+    Order { order: Box<OrderInfo> },
+    UnknownOid,
+}
+
+/// A user's referral state: who referred them, their referrer stage and referral rewards.
 #[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ReferralResponse {
-    pub data: ReferrerState,
+    pub referred_by: Option<Referrer>,
+    pub cum_vlm: String,
+    pub unclaimed_rewards: String,
+    pub claimed_rewards: String,
+    #[serde(default)]
+    pub builder_rewards: Option<String>,
+    pub referrer_state: ReferrerState,
 }
 
-#[derive(Deserialize, Debug)]
+/// A funding payment of a user from `userFunding`.
+#[derive(Deserialize, Clone, Debug)]
 pub struct UserFundingResponse {
-    pub data: Delta,
+    pub time: u64,
+    pub hash: String,
+    pub delta: Delta,
 }
 
-#[derive(serde::Deserialize, Debug)]
+#[derive(serde::Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct L2SnapshotResponse {
-    pub data: L2Data,
+    pub coin: String,
+    pub time: u64,
+    /// `(bids, asks)`, best price first.
+    pub levels: (Vec<Level>, Vec<Level>),
 }
 
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct L2Data {
-    pub asks: Vec<Level>,
-    pub bids: Vec<Level>,
-}
+impl L2SnapshotResponse {
+    pub fn bids(&self) -> &[Level] {
+        &self.levels.0
+    }
 
-#[derive(serde::Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct RecentTradesResponse {
-    pub data: Vec<Trade>,
+    pub fn asks(&self) -> &[Level] {
+        &self.levels.1
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct MetaResponse {
-    pub(crate) meta: Meta,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct SpotMetaResponse {
-    pub(crate) spot_meta: SpotMeta,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct StakingInfoResponse {
-    pub(crate) staking_info: StakingInfo,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct UserStakingInfoResponse {
-    pub(crate) user_staking_info: UserStakingInfo,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub(crate) struct StakingRewardsInfoResponse {
-    pub(crate) staking_rewards_info: StakingRewardsInfo,
-}
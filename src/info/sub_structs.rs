@@ -1,4 +1,5 @@
 use alloy_primitives::Address;
+use crate::{errors::HyperliquidError, exchange::TokenSpec, helpers::decimal_to_wei, ws::OrderStatus};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -34,7 +35,7 @@ pub struct PositionData {
     pub cum_funding: CumulativeFunding,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AssetPosition {
     pub position: PositionData,
     #[serde(rename = "type")]
@@ -50,14 +51,17 @@ pub struct MarginSummary {
     pub total_raw_usd: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Level {
     pub n: u64,
     pub px: String,
     pub sz: String,
 }
 
-#[derive(Deserialize, Debug)]
+/// A funding payment on a position of `szi` in `coin`. `n_samples` is the number of premium
+/// samples the rate was averaged over, when the venue reports it.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct Delta {
     #[serde(rename = "type")]
     pub type_string: String,
@@ -65,9 +69,22 @@ pub struct Delta {
     pub usdc: String,
     pub szi: String,
     pub funding_rate: String,
+    #[serde(default)]
+    pub n_samples: Option<u64>,
+}
+
+/// The next funding rate a venue is expected to charge on a coin.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictedFunding {
+    pub funding_rate: String,
+    pub next_funding_time: u64,
+    #[serde(default)]
+    pub funding_interval_hours: Option<u32>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct DailyUserVlm {
     pub date: String,
     pub exchange: String,
@@ -75,48 +92,117 @@ pub struct DailyUserVlm {
     pub user_cross: String,
 }
 
-#[derive(Deserialize, Debug)]
+/// The venue's fee schedule. Rates are fractions of notional; spot rates are reported separately.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct FeeSchedule {
     pub add: String,
     pub cross: String,
+    #[serde(default)]
+    pub spot_add: Option<String>,
+    #[serde(default)]
+    pub spot_cross: Option<String>,
     pub referral_discount: String,
     pub tiers: Tiers,
+    #[serde(default)]
+    pub staking_discount_tiers: Vec<StakingDiscount>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Tiers {
     pub mm: Vec<Mm>,
     pub vip: Vec<Vip>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct Mm {
     pub add: String,
     pub maker_fraction_cutoff: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct Vip {
     pub add: String,
     pub cross: String,
+    #[serde(default)]
+    pub spot_add: Option<String>,
+    #[serde(default)]
+    pub spot_cross: Option<String>,
     pub ntl_cutoff: String,
 }
 
-#[derive(Deserialize, Debug)]
+/// Fee discount for staking at least `bps_of_max_supply` of the HYPE max supply.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingDiscount {
+    pub bps_of_max_supply: String,
+    pub discount: String,
+}
+
+/// What an address is to the venue, from `userRole`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "role", content = "data", rename_all = "camelCase")]
+pub enum UserRole {
+    User,
+    /// An approved agent trading for `user`.
+    Agent { user: Address },
+    Vault,
+    /// A sub-account of `master`.
+    SubAccount { master: Address },
+    /// The address never interacted with the venue.
+    Missing,
+}
+
+/// The request budget of a user from `userRateLimit`. The cap grows with traded volume.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRateLimit {
+    pub cum_vlm: String,
+    pub n_requests_used: u64,
+    pub n_requests_cap: u64,
+}
+
+impl UserRateLimit {
+    /// Requests that can still be sent before hitting the cap.
+    pub fn remaining(&self) -> u64 {
+        self.n_requests_cap.saturating_sub(self.n_requests_used)
+    }
+}
+
+/// An agent approved by a user, from `extraAgents`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraAgent {
+    pub address: Address,
+    pub name: String,
+    /// When the approval expires, in ms since the epoch.
+    pub valid_until: u64,
+}
+
+/// A spot balance from `spotClearinghouseState`. `hold` is the part reserved by open orders and
+/// `entry_ntl` the USDC notional the balance was acquired at.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct UserTokenBalance {
     pub coin: String,
+    pub token: u32,
     pub hold: String,
     pub total: String,
+    pub entry_ntl: String,
 }
 
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderInfo {
     pub order: BasicOrderInfo,
-    pub status: String,
+    pub status: OrderStatus,
     pub status_timestamp: u64,
 }
 
+/// An order with its trigger details, as `frontendOpenOrders` and `orderStatus` report it.
+/// `children` are the TP/SL orders attached to it.
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct BasicOrderInfo {
@@ -133,27 +219,47 @@ pub struct BasicOrderInfo {
     pub reduce_only: bool,
     pub order_type: String,
     pub orig_sz: String,
-    pub tif: String,
+    /// Not set for trigger orders.
+    pub tif: Option<String>,
     pub cloid: Option<String>,
+    #[serde(default)]
+    pub children: Vec<BasicOrderInfo>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct Referrer {
     pub referrer: Address,
     pub code: String,
 }
 
+/// Progress of a user towards referring others.
 #[derive(Deserialize, Debug, Clone)]
-#[serde(rename_all = "camelCase")]
-pub struct ReferrerState {
-    pub stage: String,
-    pub data: ReferrerData,
+#[serde(rename_all = "camelCase", tag = "stage", content = "data")]
+pub enum ReferrerState {
+    /// The user has a referral code that others can use.
+    Ready(ReferrerData),
+    /// The user may create a code with `registerReferrer`.
+    NeedToCreateCode,
+    /// The user has to trade `required` more volume before creating a code.
+    NeedToTrade { required: String },
 }
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ReferrerData {
-    pub required: String,
+    pub code: String,
+    pub referral_states: Vec<ReferredUser>,
+}
+
+/// A user referred with the referrer's code.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferredUser {
+    pub user: Address,
+    pub time_joined: u64,
+    pub cum_vlm: String,
+    pub cum_rewarded_fees_since_referred: String,
+    pub cum_fees_rewarded_to_referrer: String,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -164,10 +270,385 @@ pub struct Position {
     pub type_string: String,
 }
 
+/// A user's perp account from `clearinghouseState`.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UserState {
-    pub user: Address,
     pub margin_summary: MarginSummary,
-    pub positions: Vec<Position>,
+    /// Same as `margin_summary`, counting cross margin positions only.
+    pub cross_margin_summary: MarginSummary,
+    pub cross_maintenance_margin_used: String,
+    pub withdrawable: String,
+    pub asset_positions: Vec<AssetPosition>,
+    pub time: u64,
+}
+
+/// A resting order from `openOrders`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrder {
+    pub coin: String,
+    pub side: String,
+    pub limit_px: String,
+    pub sz: String,
+    pub oid: u64,
+    pub timestamp: u64,
+    pub orig_sz: String,
+    #[serde(default)]
+    pub cloid: Option<String>,
+}
+
+/// Deploy progress of every spot token `user` is deploying, plus the current deploy gas auction.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotDeployState {
+    pub states: Vec<SpotDeployTokenState>,
+    pub gas_auction: GasAuction,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotDeployTokenState {
+    pub token: u32,
+    pub spec: TokenSpec,
+    pub full_name: Option<String>,
+    #[serde(default)]
+    pub spots: Vec<u32>,
+    #[serde(default, deserialize_with = "deserialize_optional_decimal")]
+    pub max_supply: Option<String>,
+    pub hyperliquidity_genesis_balance: String,
+    pub total_genesis_balance_wei: String,
+    pub user_genesis_balances: Vec<(Address, String)>,
+    pub existing_token_genesis_balances: Vec<(u32, String)>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GasAuction {
+    pub start_time_seconds: u64,
+    pub duration_seconds: u64,
+    pub start_gas: String,
+    pub current_gas: Option<String>,
+    pub end_gas: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDetails {
+    pub name: String,
+    pub max_supply: String,
+    pub total_supply: String,
+    pub circulating_supply: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+    pub mid_px: Option<String>,
+    pub mark_px: Option<String>,
+    pub prev_day_px: Option<String>,
+    pub genesis: Option<TokenGenesis>,
+    pub deployer: Option<Address>,
+    pub deploy_gas: Option<String>,
+    pub deploy_time: Option<String>,
+    pub seeded_usdc: String,
+    #[serde(default)]
+    pub non_circulating_user_balances: Vec<(Address, String)>,
+    pub future_emissions: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenGenesis {
+    pub user_balances: Vec<(Address, String)>,
+    pub existing_token_balances: Vec<(u32, String)>,
+}
+
+/// `maxSupply` in the deploy state is a JSON number rather than the usual decimal string.
+fn deserialize_optional_decimal<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(
+        match Option::<serde_json::Value>::deserialize(deserializer)? {
+            Some(serde_json::Value::String(value)) => Some(value),
+            Some(serde_json::Value::Number(value)) => Some(value.to_string()),
+            _ => None,
+        },
+    )
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorSummary {
+    pub delegated: String,
+    pub undelegated: String,
+    pub total_pending_withdrawal: String,
+    pub n_pending_withdrawals: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Delegation {
+    pub validator: Address,
+    pub amount: String,
+    pub locked_until_timestamp: u64,
+}
+
+impl Delegation {
+    /// Whether the delegation can not be undelegated yet at `timestamp` (in ms).
+    pub fn is_locked(&self, timestamp: u64) -> bool {
+        timestamp < self.locked_until_timestamp
+    }
+
+    /// The delegated amount in wei; staked HYPE has 8 decimals.
+    pub fn amount_wei(&self) -> Result<u64, HyperliquidError> {
+        decimal_to_wei(&self.amount, HYPE_STAKING_DECIMALS).ok_or_else(|| {
+            HyperliquidError::InvalidResponse(format!("invalid delegation amount {}", self.amount))
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DelegatorReward {
+    pub time: u64,
+    /// `delegation` for staking rewards, `commission` for validator commission.
+    pub source: String,
+    pub total_amount: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct DelegatorHistoryEntry {
+    pub time: u64,
+    pub hash: String,
+    pub delta: DelegatorDelta,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum DelegatorDelta {
+    Delegate {
+        validator: Address,
+        amount: String,
+        #[serde(rename = "isUndelegate")]
+        is_undelegate: bool,
+    },
+    CDeposit {
+        amount: String,
+    },
+    Withdrawal {
+        amount: String,
+        phase: String,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorSummary {
+    pub validator: Address,
+    pub signer: Address,
+    pub name: String,
+    pub description: String,
+    pub n_recent_blocks: u64,
+    /// Total stake in wei.
+    pub stake: u64,
+    pub is_jailed: bool,
+    pub unjailable_after: Option<u64>,
+    pub is_active: bool,
+    pub commission: String,
+    /// Stats per period (`day`, `week`, `month`).
+    pub stats: Vec<(String, ValidatorStats)>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStats {
+    pub uptime_fraction: String,
+    pub predicted_apr: String,
+    pub n_samples: u64,
+}
+
+const HYPE_STAKING_DECIMALS: u32 = 8;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDetails {
+    pub name: String,
+    pub vault_address: Address,
+    pub leader: Address,
+    pub description: String,
+    /// Account value and pnl history per period (`day`, `week`, `month`, `allTime`).
+    pub portfolio: Vec<(String, PortfolioHistory)>,
+    pub apr: f64,
+    /// The queried user's position in the vault, if any.
+    pub follower_state: Option<VaultFollower>,
+    /// Share of the vault's equity owned by the leader.
+    pub leader_fraction: f64,
+    pub leader_commission: f64,
+    pub followers: Vec<VaultFollower>,
+    pub max_distributable: f64,
+    pub max_withdrawable: f64,
+    pub is_closed: bool,
+    pub relationship: VaultRelationship,
+    pub allow_deposits: bool,
+    pub always_close_on_withdraw: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PortfolioHistory {
+    /// `(time in ms, value)` pairs.
+    #[serde(deserialize_with = "deserialize_decimal_series")]
+    pub account_value_history: Vec<(u64, f64)>,
+    #[serde(deserialize_with = "deserialize_decimal_series")]
+    pub pnl_history: Vec<(u64, f64)>,
+    pub vlm: String,
+}
+
+impl PortfolioHistory {
+    /// Returns between consecutive points, as `(time in ms, return)` pairs. Each return is the
+    /// pnl made over the step relative to the account value at its start, so deposits and
+    /// withdrawals don't count as performance. Steps starting from a zero account value are
+    /// skipped.
+    pub fn returns(&self) -> Vec<(u64, f64)> {
+        self.pnl_history
+            .windows(2)
+            .zip(&self.account_value_history)
+            .filter(|(_, (_, value))| *value > 0.0)
+            .map(|(pnl, (_, value))| (pnl[1].0, (pnl[1].1 - pnl[0].1) / value))
+            .collect()
+    }
+
+    /// Drawdown and return statistics of the series, or `None` with fewer than two returns.
+    pub fn stats(&self) -> Option<PortfolioStats> {
+        let returns: Vec<f64> = self.returns().into_iter().map(|(_, r)| r).collect();
+        if returns.len() < 2 {
+            return None;
+        }
+
+        let mut index = 1.0;
+        let mut peak = 1.0;
+        let mut max_drawdown: f64 = 0.0;
+        for r in &returns {
+            index *= 1.0 + r;
+            peak = f64::max(peak, index);
+            max_drawdown = max_drawdown.max((peak - index) / peak);
+        }
+
+        let n = returns.len() as f64;
+        let mean_return = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean_return).powi(2)).sum::<f64>() / (n - 1.0);
+        let volatility = variance.sqrt();
+        Some(PortfolioStats {
+            total_return: index - 1.0,
+            max_drawdown,
+            mean_return,
+            volatility,
+            sharpe_ratio: if volatility > 0.0 { mean_return / volatility } else { 0.0 },
+        })
+    }
+}
+
+/// Performance of a [`PortfolioHistory`]; returns are fractions per step of the series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PortfolioStats {
+    /// Compounded return over the whole series.
+    pub total_return: f64,
+    /// Largest peak-to-trough fall of the compounded returns, as a fraction of the peak.
+    pub max_drawdown: f64,
+    pub mean_return: f64,
+    /// Sample standard deviation of the returns.
+    pub volatility: f64,
+    /// `mean_return / volatility`, with no risk-free rate and not annualized.
+    pub sharpe_ratio: f64,
+}
+
+/// The periods `portfolio` reports; the `Perp` ones only count the perp account.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum PortfolioPeriod {
+    Day,
+    Week,
+    Month,
+    AllTime,
+    PerpDay,
+    PerpWeek,
+    PerpMonth,
+    PerpAllTime,
+}
+
+/// Account value and pnl history of a user per [`PortfolioPeriod`].
+#[derive(Deserialize, Debug, Clone)]
+#[serde(transparent)]
+pub struct Portfolio {
+    pub periods: Vec<(PortfolioPeriod, PortfolioHistory)>,
+}
+
+impl Portfolio {
+    pub fn get(&self, period: PortfolioPeriod) -> Option<&PortfolioHistory> {
+        self.periods.iter().find(|(p, _)| *p == period).map(|(_, history)| history)
+    }
+}
+
+/// `[time, "decimal"]` pairs with the decimals parsed.
+fn deserialize_decimal_series<'de, D>(deserializer: D) -> Result<Vec<(u64, f64)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<(u64, String)>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(time, value)| {
+            value
+                .parse()
+                .map(|value| (time, value))
+                .map_err(|_| serde::de::Error::custom(format!("invalid decimal {value}")))
+        })
+        .collect()
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFollower {
+    /// Follower address, or `Leader` for the vault leader.
+    pub user: String,
+    pub vault_equity: String,
+    pub pnl: String,
+    pub all_time_pnl: String,
+    pub days_following: u64,
+    pub vault_entry_time: u64,
+    pub lockup_until: u64,
+}
+
+impl VaultFollower {
+    /// Whether the follower can not withdraw yet at `timestamp` (in ms).
+    pub fn is_locked(&self, timestamp: u64) -> bool {
+        timestamp < self.lockup_until
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum VaultRelationship {
+    Normal,
+    #[serde(rename_all = "camelCase")]
+    Parent {
+        child_addresses: Vec<Address>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Child {
+        parent_address: Address,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserVaultEquity {
+    pub vault_address: Address,
+    pub equity: String,
+    pub locked_until_timestamp: u64,
+}
+
+impl UserVaultEquity {
+    /// Whether the equity can not be withdrawn yet at `timestamp` (in ms).
+    pub fn is_locked(&self, timestamp: u64) -> bool {
+        timestamp < self.locked_until_timestamp
+    }
 }
@@ -1,69 +1,18 @@
+use alloy_primitives::Address;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use crate::info::sub_structs::UserState;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserTokenBalance {
-    pub token: String,
-    pub free: String,
-    pub locked: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserFee {
-    pub tier: u32,
-    pub maker_rate: String,
-    pub taker_rate: String,
-    pub total_volume: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+/// A funding rate of `coin` from `fundingHistory`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FundingRate {
-    pub timestamp: u64,
-    pub rate: String,
-    pub asset: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SpotMetaAsset {
-    pub name: String,
-    pub decimals: u32,
-    pub lot_size: String,
-    pub tick_size: String,
-    pub min_size: String,
-    pub maker_fee: String,
-    pub taker_fee: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SpotMeta {
-    pub assets: HashMap<String, SpotMetaAsset>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct AssetContext {
-    pub asset: String,
-    pub price: String,
-    pub timestamp: u64,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct SpotMetaAndAssetContexts {
-    pub meta: SpotMeta,
-    pub contexts: Vec<AssetContext>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Candle {
-    pub timestamp: u64,
-    pub open: String,
-    pub high: String,
-    pub low: String,
-    pub close: String,
-    pub volume: String,
+    pub coin: String,
+    pub funding_rate: String,
+    pub premium: String,
+    pub time: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Fill {
     pub coin: String,
     pub px: String,
@@ -71,6 +20,16 @@ pub struct Fill {
     pub side: String,
     pub time: u64,
     pub hash: String,
+    pub start_position: String,
+    pub dir: String,
+    pub closed_pnl: String,
+    pub oid: u64,
+    pub tid: u64,
+    pub crossed: bool,
+    pub fee: String,
+    pub fee_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builder_fee: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,66 +40,7 @@ pub struct Trade {
     pub sz: String,
     pub time: u64,
     pub hash: String,
+    pub tid: u64,
+    /// `[buyer, seller]`.
+    pub users: [Address; 2],
 }
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StakingInfo {
-    pub total_staked: String,
-    pub total_rewards: String,
-    pub apr: String,
-    pub min_stake: String,
-    pub max_stake: String,
-    pub cooldown_period: u64,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserStakingInfo {
-    pub staked_amount: String,
-    pub pending_rewards: String,
-    pub cooldown_end: Option<u64>,
-    pub unstake_amount: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct StakingRewardsInfo {
-    pub total_distributed: String,
-    pub current_epoch: u64,
-    pub rewards_per_epoch: String,
-    pub epoch_duration: u64,
-}
-
-// Response types
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct UserTokenBalancesResponse {
-    pub data: Vec<UserTokenBalance>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct UserFeesResponse {
-    pub data: UserFee,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct FundingHistoryResponse {
-    pub data: Vec<FundingRate>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct SpotMetaResponse {
-    pub data: SpotMeta,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct SpotMetaAndAssetContextsResponse {
-    pub data: SpotMetaAndAssetContexts,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct CandlesResponse {
-    pub data: Vec<Candle>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub(crate) struct UserStatesResponse {
-    pub data: Vec<UserState>,
-} 
\ No newline at end of file
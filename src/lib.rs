@@ -1,6 +1,7 @@
 #![deny(unreachable_pub)]
 
 // Internal modules
+pub mod bridge;
 mod constants;
 mod errors;
 mod exchange;
@@ -19,20 +20,33 @@ pub use alloy_primitives::{Address, B256, U256};
 pub use alloy_signer_local::PrivateKeySigner as LocalWallet;
 
 pub use constants::{Chain, EPSILON, LOCAL_API_URL, MAINNET_API_URL, TESTNET_API_URL};
-pub use errors::Error;
+pub use errors::{Error, HyperliquidError};
 pub use exchange::*;
-pub use helpers::{bps_diff, truncate_float, BaseUrl};
+pub use helpers::{bps_diff, round_px, round_sz, truncate_float, BaseUrl, NonceManager, RateLimiter};
 pub use info::{
     InfoClient,
+    InfoQuery,
     // Other available exports from info
 };
 pub use market_maker::{MarketMaker, MarketMakerInput, MarketMakerRestingOrder};
-pub use meta::{AssetMeta, Meta, SpotMetaAndAssetCtxs, SpotAssetContext};
-pub use signature::create_signature::SignatureBytes;
+pub use meta::{
+    AssetMeta, EvmContract, MarginTable, MarginTier, Meta, MetaAndAssetCtxs, PerpDex,
+    SpotAssetContext, SpotAssetMeta, SpotMeta, SpotMetaAndAssetCtxs, SpotValuation, TokenInfo,
+};
+pub use signature::{
+    create_signature::{SignatureBytes, SignatureRsv},
+    eip712, HyperliquidSigner,
+};
 pub use ws::{
     Candle,
+    CandleData,
+    CandleInterval,
+    L2BookOptions,
+    OrderStatus,
     Trade,
     Message,
     Subscription,
     WsManager,
+    PerpsAssetCtx,
+    SharedAssetCtx,
 };
@@ -64,7 +64,10 @@ impl MarketMaker {
         let user_address = input.wallet.address();
 
         let info_client = InfoClient::new(BaseUrl::Testnet.get_url());
-        let exchange_client = ExchangeClient::new(BaseUrl::Testnet.get_url());
+        let exchange_client =
+            ExchangeClient::new_with_wallet(BaseUrl::Testnet.get_url(), input.wallet, None)
+                .await
+                .unwrap();
 
         MarketMaker {
             asset: input.asset,
@@ -163,7 +166,7 @@ impl MarketMaker {
     async fn attempt_cancel(&self, asset: String, oid: u64) -> bool {
         let cancel = self
             .exchange_client
-            .cancel(ClientCancelRequest { asset, oid }, None)
+            .cancel(ClientCancelRequest { asset, oid })
             .await;
 
         match cancel {
@@ -225,10 +228,10 @@ impl MarketMaker {
                         if !order.statuses.is_empty() {
                             match order.statuses[0].clone() {
                                 ExchangeDataStatus::Filled(order) => {
-                                    return (amount, order.order.oid);
+                                    return (amount, order.oid);
                                 }
                                 ExchangeDataStatus::Resting(order) => {
-                                    return (amount, order.order.oid);
+                                    return (amount, order.oid);
                                 }
                                 ExchangeDataStatus::Error(e) => {
                                     error!("Error with placing order: {e}")
@@ -1,11 +1,87 @@
 use std::collections::HashMap;
 
-use alloy_primitives::U128;
+use alloy_primitives::{Address, U128};
 use serde::Deserialize;
 
+use crate::{
+    constants::{
+        HYPE_SYSTEM_ADDRESS, HYPE_TOKEN_NAME, PERP_DEX_OFFSET_BASE, PERP_DEX_OFFSET_STRIDE,
+        SPOT_ASSET_OFFSET, SYSTEM_ADDRESS_PREFIX,
+    },
+    errors::{HyperliquidError, Result},
+    info::sub_structs::UserTokenBalance,
+    ws::PerpsAssetCtx,
+};
+
+/// Index of USDC in the spot token list; every spot pair is quoted against it.
+pub(crate) const USDC_TOKEN_INDEX: usize = 0;
+
 #[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct Meta {
     pub universe: Vec<AssetMeta>,
+    /// Margin tables referenced by [`AssetMeta::margin_table_id`], as `(id, table)` pairs.
+    #[serde(default)]
+    pub margin_tables: Vec<(u32, MarginTable)>,
+    /// Index of the spot token perps of the DEX are margined in.
+    #[serde(default)]
+    pub collateral_token: Option<u32>,
+}
+
+impl Meta {
+    pub fn margin_table(&self, id: u32) -> Option<&MarginTable> {
+        self.margin_tables
+            .iter()
+            .find(|(table_id, _)| *table_id == id)
+            .map(|(_, table)| table)
+    }
+
+    /// Adds the assets of `dex` (empty for the default perp DEX) to the coin and size decimals
+    /// maps. Coins of builder-deployed DEXs are keyed as `dex:COIN`.
+    pub(crate) fn add_to_index_maps(
+        &self,
+        dex: &str,
+        offset: u32,
+        coin_to_asset: &mut HashMap<String, u32>,
+        sz_decimals: &mut HashMap<u32, u32>,
+    ) {
+        let prefix = format!("{dex}:");
+        for (index, asset) in self.universe.iter().enumerate() {
+            let asset_ind = offset + index as u32;
+            let name = if dex.is_empty() || asset.name.starts_with(&prefix) {
+                asset.name.clone()
+            } else {
+                format!("{prefix}{}", asset.name)
+            };
+            coin_to_asset.insert(name, asset_ind);
+            sz_decimals.insert(asset_ind, asset.sz_decimals);
+        }
+    }
+}
+
+/// A builder-deployed perp DEX from `perpDexs`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpDex {
+    pub name: String,
+    pub full_name: String,
+    pub deployer: Address,
+    pub oracle_updater: Option<Address>,
+    #[serde(default)]
+    pub fee_recipient: Option<Address>,
+}
+
+/// Asset id offset of the perp DEX at `dex_index` in the `perpDexs` list, where index 0 is the
+/// default DEX.
+pub(crate) fn perp_dex_offset(dex_index: usize) -> u32 {
+    match dex_index {
+        0 => 0,
+        n => PERP_DEX_OFFSET_BASE + (n as u32 - 1) * PERP_DEX_OFFSET_STRIDE,
+    }
+}
+
+pub(crate) fn is_spot_asset(asset: u32) -> bool {
+    (SPOT_ASSET_OFFSET..PERP_DEX_OFFSET_BASE).contains(&asset)
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -26,7 +102,7 @@ impl SpotMeta {
             .collect();
 
         for asset in self.universe.iter() {
-            let spot_ind: u32 = 10000 + asset.index as u32;
+            let spot_ind: u32 = SPOT_ASSET_OFFSET + asset.index as u32;
             let name_to_ind = (asset.name.clone(), spot_ind);
 
             let Some(token_1_name) = index_to_name.get(&asset.tokens[0]) else {
@@ -38,18 +114,84 @@ impl SpotMeta {
             };
 
             coin_to_asset.insert(format!("{}/{}", token_1_name, token_2_name), spot_ind);
+            coin_to_asset.insert(format!("@{}", asset.index), spot_ind);
             coin_to_asset.insert(name_to_ind.0, name_to_ind.1);
         }
 
         coin_to_asset
     }
+
+    /// Size decimals of every spot asset, keyed by asset id. A pair trades in units of its base
+    /// token, so it takes that token's size decimals.
+    pub(crate) fn asset_sz_decimals(&self) -> HashMap<u32, u32> {
+        let index_to_decimals: HashMap<usize, u32> = self
+            .tokens
+            .iter()
+            .map(|info| (info.index, info.sz_decimals as u32))
+            .collect();
+
+        self.universe
+            .iter()
+            .filter_map(|asset| {
+                let sz_decimals = index_to_decimals.get(&asset.tokens[0])?;
+                Some((SPOT_ASSET_OFFSET + asset.index as u32, *sz_decimals))
+            })
+            .collect()
+    }
+
+    /// Values `balances` in USDC, pricing each token at the mid of its USDC pair in `mids` (as
+    /// returned by `allMids`). Zero balances are skipped; tokens without a USDC pair or mid are
+    /// reported as unpriced rather than failing the valuation.
+    pub fn usdc_value(
+        &self,
+        balances: &[UserTokenBalance],
+        mids: &HashMap<String, String>,
+    ) -> Result<SpotValuation> {
+        let mut valuation = SpotValuation::default();
+        for balance in balances {
+            let amount = parse_decimal(&balance.total)?;
+            if amount == 0.0 {
+                continue;
+            }
+            if balance.token as usize == USDC_TOKEN_INDEX {
+                valuation.total += amount;
+                continue;
+            }
+
+            let mid = self
+                .universe
+                .iter()
+                .find(|asset| asset.tokens == [balance.token as usize, USDC_TOKEN_INDEX])
+                .and_then(|pair| mids.get(&pair.name));
+            match mid {
+                Some(mid) => valuation.total += amount * parse_decimal(mid)?,
+                None => valuation.unpriced.push(balance.coin.clone()),
+            }
+        }
+        Ok(valuation)
+    }
+}
+
+/// USDC value of spot balances from [`SpotMeta::usdc_value`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpotValuation {
+    /// Value of the balances that could be priced.
+    pub total: f64,
+    /// Coins of the non-zero balances left out of `total`, for lack of a USDC pair or a mid.
+    pub unpriced: Vec<String>,
 }
 
+pub(crate) fn parse_decimal(value: &str) -> Result<f64> {
+    value
+        .parse()
+        .map_err(|_| HyperliquidError::SerializationError(format!("invalid decimal {value}")))
+}
+
+/// One element of the `spotMetaAndAssetCtxs` response, which is a `[meta, contexts]` pair.
 #[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
 pub enum SpotMetaAndAssetCtxs {
-    #[serde(rename = "meta")]
     SpotMeta(SpotMeta),
-    #[serde(rename = "context")]
     Context(Vec<SpotAssetContext>),
 }
 
@@ -69,6 +211,79 @@ pub struct SpotAssetContext {
 pub struct AssetMeta {
     pub name: String,
     pub sz_decimals: u32,
+    pub max_leverage: u32,
+    #[serde(default)]
+    pub margin_table_id: Option<u32>,
+    /// Positions in the asset can only be opened with isolated margin.
+    #[serde(default)]
+    pub only_isolated: bool,
+    #[serde(default)]
+    pub is_delisted: bool,
+}
+
+/// Maximum leverage by position notional: each tier applies from its lower bound up to the
+/// next tier's.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTable {
+    pub description: String,
+    pub margin_tiers: Vec<MarginTier>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginTier {
+    pub lower_bound: String,
+    pub max_leverage: u32,
+}
+
+/// The `metaAndAssetCtxs` response: the perp meta and the context of each of its assets, in
+/// the same order.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "(Meta, Vec<PerpsAssetCtx>)")]
+pub struct MetaAndAssetCtxs {
+    pub meta: Meta,
+    pub asset_ctxs: Vec<PerpsAssetCtx>,
+    coin_to_index: HashMap<String, usize>,
+}
+
+impl TryFrom<(Meta, Vec<PerpsAssetCtx>)> for MetaAndAssetCtxs {
+    type Error = String;
+
+    fn try_from(
+        (meta, asset_ctxs): (Meta, Vec<PerpsAssetCtx>),
+    ) -> std::result::Result<Self, String> {
+        if meta.universe.len() != asset_ctxs.len() {
+            return Err(format!(
+                "{} assets but {} asset contexts",
+                meta.universe.len(),
+                asset_ctxs.len()
+            ));
+        }
+        let coin_to_index = meta
+            .universe
+            .iter()
+            .enumerate()
+            .map(|(index, asset)| (asset.name.clone(), index))
+            .collect();
+        Ok(Self {
+            meta,
+            asset_ctxs,
+            coin_to_index,
+        })
+    }
+}
+
+impl MetaAndAssetCtxs {
+    /// Every asset with its context.
+    pub fn assets(&self) -> impl Iterator<Item = (&AssetMeta, &PerpsAssetCtx)> {
+        self.meta.universe.iter().zip(&self.asset_ctxs)
+    }
+
+    pub fn get(&self, coin: &str) -> Option<(&AssetMeta, &PerpsAssetCtx)> {
+        let index = *self.coin_to_index.get(coin)?;
+        Some((&self.meta.universe[index], &self.asset_ctxs[index]))
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -89,4 +304,150 @@ pub struct TokenInfo {
     pub index: usize,
     pub token_id: U128,
     pub is_canonical: bool,
+    /// The ERC-20 contract the token is linked to on HyperEVM, if any.
+    #[serde(default)]
+    pub evm_contract: Option<EvmContract>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvmContract {
+    pub address: Address,
+    /// Decimals the EVM token has beyond the token's wei decimals.
+    pub evm_extra_wei_decimals: i32,
+}
+
+impl TokenInfo {
+    /// The `NAME:0xtokenId` form transfers identify the token by.
+    pub fn wire_name(&self) -> String {
+        format!(
+            "{}:0x{}",
+            self.name,
+            hex::encode(self.token_id.to_be_bytes::<16>())
+        )
+    }
+
+    /// The address spot transfers of the token are sent to in order to move them to HyperEVM.
+    pub fn system_address(&self) -> Address {
+        if self.name == HYPE_TOKEN_NAME {
+            return HYPE_SYSTEM_ADDRESS;
+        }
+        let mut address = [0u8; 20];
+        address[0] = SYSTEM_ADDRESS_PREFIX;
+        address[12..].copy_from_slice(&(self.index as u64).to_be_bytes());
+        Address::from(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spot_meta() -> SpotMeta {
+        serde_json::from_str(
+            r#"{
+                "tokens": [
+                    {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+                    {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true},
+                    {"name":"HFUN","szDecimals":2,"weiDecimals":8,"index":2,"tokenId":"0xbaf265ef389da684513d98d68edf4eae","isCanonical":false,"evmContract":{"address":"0xa320d9f65ec992eff38622c63627856382db726c","evm_extra_wei_decimals":10}},
+                    {"name":"HYPE","szDecimals":2,"weiDecimals":8,"index":150,"tokenId":"0x0d01dc56dcaaca66ad901c959b4011ec","isCanonical":false,"evmContract":null}
+                ],
+                "universe": [
+                    {"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true},
+                    {"tokens":[2,0],"name":"@1","index":1,"isCanonical":false}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_spot_asset_lookup() {
+        let meta = spot_meta();
+        let coin_to_asset = meta.add_pair_and_name_to_index_map(HashMap::new());
+        assert_eq!(coin_to_asset["PURR/USDC"], 10000);
+        assert_eq!(coin_to_asset["@0"], 10000);
+        assert_eq!(coin_to_asset["HFUN/USDC"], 10001);
+        assert_eq!(coin_to_asset["@1"], 10001);
+
+        let sz_decimals = meta.asset_sz_decimals();
+        assert_eq!(sz_decimals[&10000], 0);
+        assert_eq!(sz_decimals[&10001], 2);
+    }
+
+    #[test]
+    fn test_token_wire_name() {
+        let meta = spot_meta();
+        assert_eq!(
+            meta.tokens[1].wire_name(),
+            "PURR:0xc1fb593aeffbeb02f85e0308e9956a90"
+        );
+    }
+
+    #[test]
+    fn test_token_system_address() {
+        let meta = spot_meta();
+        assert_eq!(
+            meta.tokens[2].system_address(),
+            "0x2000000000000000000000000000000000000002"
+                .parse::<Address>()
+                .unwrap()
+        );
+        let evm_contract = meta.tokens[2].evm_contract.as_ref().unwrap();
+        assert_eq!(evm_contract.evm_extra_wei_decimals, 10);
+        assert!(meta.tokens[1].evm_contract.is_none());
+
+        assert_eq!(
+            meta.tokens[3].system_address(),
+            "0x2222222222222222222222222222222222222222"
+                .parse::<Address>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_perp_dex_assets() {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe":[{"name":"test:ABC","szDecimals":2,"maxLeverage":10},{"name":"XYZ","szDecimals":0,"maxLeverage":10}]}"#,
+        )
+        .unwrap();
+        let mut coin_to_asset = HashMap::new();
+        let mut sz_decimals = HashMap::new();
+        meta.add_to_index_maps("test", perp_dex_offset(2), &mut coin_to_asset, &mut sz_decimals);
+
+        assert_eq!(coin_to_asset["test:ABC"], 120000);
+        assert_eq!(coin_to_asset["test:XYZ"], 120001);
+        assert_eq!(sz_decimals[&120000], 2);
+        assert_eq!(perp_dex_offset(0), 0);
+        assert_eq!(perp_dex_offset(1), 110000);
+        assert!(is_spot_asset(10001));
+        assert!(!is_spot_asset(120001));
+        assert!(!is_spot_asset(3));
+    }
+
+    #[test]
+    fn test_usdc_value() {
+        let meta = spot_meta();
+        let balances: Vec<UserTokenBalance> = serde_json::from_str(
+            r#"[
+                {"coin":"USDC","token":0,"hold":"0.0","total":"100.5","entryNtl":"0.0"},
+                {"coin":"PURR","token":1,"hold":"0.0","total":"1000","entryNtl":"150.0"},
+                {"coin":"HFUN","token":2,"hold":"1.0","total":"2.5","entryNtl":"40.0"},
+                {"coin":"JEFF","token":3,"hold":"0.0","total":"0.0","entryNtl":"0.0"}
+            ]"#,
+        )
+        .unwrap();
+        let mids = HashMap::from([
+            ("PURR/USDC".to_string(), "0.2".to_string()),
+            ("@1".to_string(), "20".to_string()),
+        ]);
+
+        let value = meta.usdc_value(&balances, &mids).unwrap();
+        assert!((value.total - 350.5).abs() < 1e-9);
+        assert!(value.unpriced.is_empty());
+
+        let mids = HashMap::from([("PURR/USDC".to_string(), "0.2".to_string())]);
+        let value = meta.usdc_value(&balances, &mids).unwrap();
+        assert!((value.total - 300.5).abs() < 1e-9);
+        assert_eq!(value.unpriced, vec!["HFUN".to_string()]);
+    }
 }
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, B256};
 use alloy_sol_types::{sol, SolType, SolValue};
 use crate::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -9,18 +9,16 @@ pub(crate) mod l1 {
     sol! {
         #[derive(Debug)]
         struct Agent {
-            address agent;
-            string name;
-            uint64 time;
+            string source;
+            bytes32 connectionId;
         }
     }
 
     impl Agent {
-        pub fn new(agent: Address, name: String, time: u64) -> Self {
+        pub fn new(connection_id: B256, is_mainnet: bool) -> Self {
             Self {
-                agent,
-                name,
-                time,
+                source: if is_mainnet { "a" } else { "b" }.to_string(),
+                connectionId: connection_id,
             }
         }
     }
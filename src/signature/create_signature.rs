@@ -1,10 +1,20 @@
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{keccak256, Address, PrimitiveSignature, B256, U256};
 use alloy_signer_local::PrivateKeySigner;
 use alloy_signer::{Signer, SignerSync};
 use alloy_sol_types::{sol, SolType, SolValue, SolStruct};
 use alloy_dyn_abi::Eip712Domain;
-use crate::{prelude::*, Error};
+use crate::{
+    prelude::*,
+    signature::{
+        agent::l1,
+        eip712::{Domain, Types, TypedData},
+    },
+    Error,
+};
 use hex;
+use serde::{Deserialize, Serialize};
+
+const L1_SIGNATURE_CHAIN_ID: u64 = 1337;
 
 #[derive(Debug, Clone)]
 pub struct SignatureBytes(pub [u8; 65]);
@@ -15,6 +25,24 @@ impl ToString for SignatureBytes {
     }
 }
 
+impl SignatureBytes {
+    pub fn to_rsv(&self) -> SignatureRsv {
+        SignatureRsv {
+            r: U256::from_be_slice(&self.0[..32]),
+            s: U256::from_be_slice(&self.0[32..64]),
+            v: self.0[64] as u64,
+        }
+    }
+}
+
+/// Signature in the `{r, s, v}` form the `/exchange` endpoint expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureRsv {
+    pub r: U256,
+    pub s: U256,
+    pub v: u64,
+}
+
 pub(crate) mod domain {
     use super::*;
 
@@ -42,20 +70,145 @@ pub(crate) mod domain {
     }
 }
 
-pub(crate) async fn sign_typed_data<T: SolStruct>(payload: &T, wallet: &PrivateKeySigner) -> Result<SignatureBytes> {
-    let domain = Eip712Domain {
-        name: Some("HyperLiquid".into()),
-        version: Some("1".into()),
-        chain_id: Some(U256::from(421614u64)),
-        verifying_contract: None,
-        salt: None,
-    };
+/// EIP-712 hash of the phantom agent wrapping an L1 action's connection id.
+pub(crate) fn l1_action_signing_hash(connection_id: B256, is_mainnet: bool) -> B256 {
+    let domain = Eip712Domain::new(
+        Some("Exchange".into()),
+        Some("1".into()),
+        Some(U256::from(L1_SIGNATURE_CHAIN_ID)),
+        Some(Address::ZERO),
+        None,
+    );
+    l1::Agent::new(connection_id, is_mainnet).eip712_signing_hash(&domain)
+}
 
-    let signature = wallet
-        .sign_typed_data_sync(payload, &domain)
-        .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+/// Typed data of the phantom agent [`l1_action_signing_hash`] hashes, for external signers.
+pub(crate) fn l1_action_typed_data(connection_id: B256, is_mainnet: bool) -> TypedData {
+    let domain = Domain::new(
+        "Exchange".to_string(),
+        "1".to_string(),
+        U256::from(L1_SIGNATURE_CHAIN_ID),
+        Some(Address::ZERO),
+        None,
+    );
+    let mut agent = Types::new();
+    agent.name = "Agent".to_string();
+    agent.add_field("source", "string");
+    agent.add_field("connectionId", "bytes32");
+    let message = serde_json::json!({
+        "source": if is_mainnet { "a" } else { "b" },
+        "connectionId": connection_id.to_string(),
+    });
+    TypedData::new(domain, agent, message)
+}
 
-    Ok(SignatureBytes(signature.as_bytes()))
+/// Typed data of a user-signed action as [`user_signed_action_hash`] hashes it, keeping only
+/// the signed fields of `message`.
+pub(crate) fn user_signed_action_typed_data(
+    primary_type: &str,
+    fields: &[(&str, &str)],
+    message: &serde_json::Value,
+    signature_chain_id: U256,
+) -> TypedData {
+    let domain = Domain::new(
+        "HyperliquidSignTransaction".to_string(),
+        "1".to_string(),
+        signature_chain_id,
+        Some(Address::ZERO),
+        None,
+    );
+    let mut types = Types::new();
+    types.name = primary_type.to_string();
+    let mut signed = serde_json::Map::new();
+    for (name, ty) in fields {
+        types.add_field(name, ty);
+        if let Some(value) = message.get(*name) {
+            signed.insert(name.to_string(), value.clone());
+        }
+    }
+    TypedData::new(domain, types, signed.into())
+}
+
+/// EIP-712 hash of a user-signed action.
+///
+/// The primary types are named `HyperliquidTransaction:<Action>`, which `alloy`'s type parser
+/// rejects, so the struct hash is encoded by hand from the action's JSON fields.
+pub(crate) fn user_signed_action_hash(
+    primary_type: &str,
+    fields: &[(&str, &str)],
+    message: &serde_json::Value,
+    signature_chain_id: U256,
+) -> Result<B256> {
+    let encoded_type = format!(
+        "{primary_type}({})",
+        fields
+            .iter()
+            .map(|(name, ty)| format!("{ty} {name}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+
+    let mut encoded = keccak256(encoded_type.as_bytes()).to_vec();
+    for (name, ty) in fields {
+        let value = message
+            .get(*name)
+            .ok_or_else(|| Error::Eip712(format!("missing field {name} for {primary_type}")))?;
+        encoded.extend_from_slice(encode_eip712_value(ty, value)?.as_slice());
+    }
+
+    let domain = Eip712Domain::new(
+        Some("HyperliquidSignTransaction".into()),
+        Some("1".into()),
+        Some(signature_chain_id),
+        Some(Address::ZERO),
+        None,
+    );
+
+    let mut digest_input = Vec::with_capacity(66);
+    digest_input.extend_from_slice(&[0x19, 0x01]);
+    digest_input.extend_from_slice(domain.separator().as_slice());
+    digest_input.extend_from_slice(keccak256(&encoded).as_slice());
+    Ok(keccak256(digest_input))
+}
+
+fn encode_eip712_value(ty: &str, value: &serde_json::Value) -> Result<B256> {
+    let invalid = || Error::Eip712(format!("invalid {ty} value: {value}"));
+    match ty {
+        "string" => Ok(keccak256(value.as_str().ok_or_else(invalid)?.as_bytes())),
+        "address" => {
+            let address = value
+                .as_str()
+                .and_then(|address| address.parse::<Address>().ok())
+                .ok_or_else(invalid)?;
+            Ok(address.into_word())
+        }
+        "bool" => Ok(U256::from(value.as_bool().ok_or_else(invalid)? as u8).into()),
+        "bytes32" => value
+            .as_str()
+            .and_then(|bytes| bytes.parse::<B256>().ok())
+            .ok_or_else(invalid),
+        "uint64" | "uint256" => {
+            let number = match value {
+                serde_json::Value::Number(number) => number.as_u64().map(U256::from),
+                serde_json::Value::String(number) => number.parse::<U256>().ok(),
+                _ => None,
+            };
+            Ok(number.ok_or_else(invalid)?.into())
+        }
+        _ => Err(Error::Eip712(format!("unsupported type {ty}"))),
+    }
+}
+
+/// Address whose key produced `signature` over `hash`.
+pub(crate) fn recover_signer(hash: B256, signature: &SignatureRsv) -> Result<Address> {
+    let y_parity = match signature.v {
+        0 | 27 => false,
+        1 | 28 => true,
+        v => return Err(Error::InvalidSignature(format!("invalid recovery id {v}"))),
+    };
+    PrimitiveSignature::new(signature.r, signature.s, y_parity)
+        .recover_address_from_prehash(&hash)
+        .map_err(|e| Error::InvalidSignature(e.to_string()))
 }
 
 pub(crate) async fn sign_l1_action(hash: B256, wallet: &PrivateKeySigner) -> Result<SignatureBytes> {
@@ -69,7 +222,7 @@ pub(crate) async fn sign_l1_action(hash: B256, wallet: &PrivateKeySigner) -> Res
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::exchange::actions::types::{UsdSend, Withdraw3};
+    use crate::exchange::actions::{UsdSend, UserSignedAction, Withdraw3};
     use std::str::FromStr;
 
     fn get_wallet() -> Result<PrivateKeySigner> {
@@ -94,22 +247,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sign_l1_action_with_phantom_agent() -> Result<()> {
+        let wallet = get_wallet()?;
+        let connection_id =
+            B256::from_str("0xde6c4037798a4434ca03cd05f00e3b803126221375cd1e7eaaaf041768be06eb")
+                .map_err(|e| Error::GenericParse(e.to_string()))?;
+
+        let expected_mainnet_sig = "fa8a41f6a3fa728206df80801a83bcbfbab08649cd34d9c0bfba7c7b2f99340f53a00226604567b98a1492803190d65a201d6805e5831b7044f17fd530aec7841c";
+        assert_eq!(
+            sign_l1_action(l1_action_signing_hash(connection_id, true), &wallet)
+                .await?
+                .to_string(),
+            expected_mainnet_sig
+        );
+
+        let expected_testnet_sig = "1713c0fc661b792a50e8ffdd59b637b1ed172d9a3aa4d801d9d88646710fb74b33959f4d075a7ccbec9f2374a6da21ffa4448d58d0413a0d335775f680a881431c";
+        assert_eq!(
+            sign_l1_action(l1_action_signing_hash(connection_id, false), &wallet)
+                .await?
+                .to_string(),
+            expected_testnet_sig
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_recover_signer() -> Result<()> {
+        let wallet = get_wallet()?;
+        let hash = l1_action_signing_hash(
+            B256::from_str("0xde6c4037798a4434ca03cd05f00e3b803126221375cd1e7eaaaf041768be06eb")
+                .map_err(|e| Error::GenericParse(e.to_string()))?,
+            true,
+        );
+        let signature = sign_l1_action(hash, &wallet).await?.to_rsv();
+        assert_eq!(recover_signer(hash, &signature)?, wallet.address());
+
+        let invalid = SignatureRsv { v: 29, ..signature };
+        assert!(recover_signer(hash, &invalid).is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sign_usd_transfer_action() -> Result<()> {
         let wallet = get_wallet()?;
 
         let usd_send = UsdSend {
-            signatureChainId: U256::from(421614u64),
-            hyperliquidChain: "Testnet".to_string(),
-            destination: Address::from_str("0x0D1d9635D0640821d15e323ac8AdADfA9c111414")
-                .map_err(|e| Error::GenericParse(e.to_string()))?,
-            amount: U256::from(1u64),
-            time: U256::from(1690393044548u64),
+            signature_chain_id: U256::from(421614u64),
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
         };
 
-        let expected_sig = "c1408a72b086344c3c0837847f60857486c81f9f69c4d05e2cb2a8de027c7bdd2d611524585e82e76eeee31c75ad332bd5d89a91f1cbfa20d039a4e2a77e30bd1c";
+        let expected_sig = "214d507bbdaebba52fa60928f904a8b2df73673e3baba6133d66fe846c7ef70451e82453a6d8db124e7ed6e60fa00d4b7c46e4d96cb2bd61fd81b6e8953cc9d21b";
         assert_eq!(
-            sign_typed_data(&usd_send, &wallet).await?.to_string(),
+            sign_l1_action(usd_send.signing_hash()?, &wallet).await?.to_string(),
             expected_sig
         );
         Ok(())
@@ -119,18 +312,17 @@ mod tests {
     async fn test_sign_withdraw_from_bridge_action() -> Result<()> {
         let wallet = get_wallet()?;
 
-        let usd_send = Withdraw3 {
-            signatureChainId: U256::from(421614u64),
-            hyperliquidChain: "Testnet".to_string(),
-            destination: Address::from_str("0x0D1d9635D0640821d15e323ac8AdADfA9c111414")
-                .map_err(|e| Error::GenericParse(e.to_string()))?,
-            amount: U256::from(1u64),
-            time: U256::from(1690393044548u64),
+        let withdraw = Withdraw3 {
+            signature_chain_id: U256::from(421614u64),
+            hyperliquid_chain: "Testnet".to_string(),
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414".to_string(),
+            amount: "1".to_string(),
+            time: 1690393044548,
         };
 
-        let expected_sig = "a9c18df3b6321fbcaf685f526a0d9356f5562dfa608f2cddea9081921815a4337050a25f2503bcfa9cf2b724178fa4c52ce705988cf7b71d6c9a1193fdc0afd11c";
+        let expected_sig = "b3172e33d2262dac2b4cb135ce3c167fda55dafa6c62213564ab728b9f9ba76b769a938e9f6d603dae7154c83bf5a4c3ebab81779dc2db25463a3ed663c82ae41c";
         assert_eq!(
-            sign_typed_data(&usd_send, &wallet).await?.to_string(),
+            sign_l1_action(withdraw.signing_hash()?, &wallet).await?.to_string(),
             expected_sig
         );
         Ok(())
@@ -1,5 +1,6 @@
 use alloy_primitives::{Address, B256, U256};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Types {
@@ -7,9 +8,10 @@ pub(crate) struct Types {
     pub fields: Vec<Field>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct Field {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
     pub name: String,
+    #[serde(rename = "type")]
     pub ty: String,
 }
 
@@ -29,12 +31,15 @@ impl Types {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct Domain {
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Domain {
     pub name: String,
     pub version: String,
     pub chain_id: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub verifying_contract: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub salt: Option<B256>,
 }
 
@@ -54,4 +59,46 @@ impl Domain {
             salt,
         }
     }
-} 
\ No newline at end of file
+
+    /// The `EIP712Domain` type listing the fields this domain sets.
+    fn types(&self) -> Types {
+        let mut types = Types::new();
+        types.name = "EIP712Domain".to_string();
+        types.add_field("name", "string");
+        types.add_field("version", "string");
+        types.add_field("chainId", "uint256");
+        if self.verifying_contract.is_some() {
+            types.add_field("verifyingContract", "address");
+        }
+        if self.salt.is_some() {
+            types.add_field("salt", "bytes32");
+        }
+        types
+    }
+}
+
+/// EIP-712 typed data in the JSON form `eth_signTypedData_v4` and hardware wallets accept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<Field>>,
+    pub primary_type: String,
+    pub domain: Domain,
+    pub message: serde_json::Value,
+}
+
+impl TypedData {
+    pub(crate) fn new(domain: Domain, primary: Types, message: serde_json::Value) -> Self {
+        let primary_type = primary.name.clone();
+        let types = [domain.types(), primary]
+            .into_iter()
+            .map(|types| (types.name, types.fields))
+            .collect();
+        Self {
+            types,
+            primary_type,
+            domain,
+            message,
+        }
+    }
+}
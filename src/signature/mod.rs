@@ -1,5 +1,10 @@
 pub(crate) mod agent;
 pub(crate) mod create_signature;
-pub(crate) mod eip712;
+pub mod eip712;
+mod signer;
 
-pub(crate) use create_signature::{sign_l1_action, sign_typed_data};
+pub(crate) use create_signature::{
+    l1_action_signing_hash, l1_action_typed_data, recover_signer, sign_l1_action,
+    user_signed_action_hash, user_signed_action_typed_data,
+};
+pub use signer::HyperliquidSigner;
@@ -0,0 +1,31 @@
+use alloy_primitives::{Address, B256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+
+use crate::{
+    errors::{HyperliquidError, Result},
+    signature::create_signature::SignatureBytes,
+};
+
+/// Signs the 32 byte digests Hyperliquid actions are authorized with.
+///
+/// Implemented for local keys; other key stores (hardware wallets, remote signers) can
+/// implement it to take part in multi-sig and offline signing workflows.
+pub trait HyperliquidSigner {
+    fn address(&self) -> Address;
+
+    fn sign_hash(&self, hash: B256) -> Result<SignatureBytes>;
+}
+
+impl HyperliquidSigner for PrivateKeySigner {
+    fn address(&self) -> Address {
+        PrivateKeySigner::address(self)
+    }
+
+    fn sign_hash(&self, hash: B256) -> Result<SignatureBytes> {
+        let signature = self
+            .sign_hash_sync(&hash)
+            .map_err(|e| HyperliquidError::SignatureError(e.to_string()))?;
+        Ok(SignatureBytes(signature.as_bytes()))
+    }
+}
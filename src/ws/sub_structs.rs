@@ -1,6 +1,10 @@
+use crate::{
+    errors::{HyperliquidError, Result},
+    meta::parse_decimal,
+};
 use alloy_primitives::Address;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, str::FromStr};
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct Trade {
@@ -20,6 +24,47 @@ pub struct BookLevel {
     pub n: u64,
 }
 
+/// Aggregation of an L2 book. `n_sig_figs` (2 to 5) rounds prices to that many significant
+/// figures; `mantissa` (1, 2 or 5) further buckets them and is only allowed with 5 figures.
+/// Leave both unset for the full-precision book.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct L2BookOptions {
+    #[serde(rename = "nSigFigs", default, skip_serializing_if = "Option::is_none")]
+    pub n_sig_figs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mantissa: Option<u32>,
+}
+
+impl L2BookOptions {
+    pub fn sig_figs(n_sig_figs: u32) -> Self {
+        Self {
+            n_sig_figs: Some(n_sig_figs),
+            mantissa: None,
+        }
+    }
+
+    pub fn with_mantissa(mut self, mantissa: u32) -> Self {
+        self.mantissa = Some(mantissa);
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if let Some(n_sig_figs) = self.n_sig_figs {
+            if !(2..=5).contains(&n_sig_figs) {
+                return Err(HyperliquidError::InvalidParameter(format!(
+                    "nSigFigs must be between 2 and 5, got {n_sig_figs}"
+                )));
+            }
+        }
+        match (self.mantissa, self.n_sig_figs) {
+            (None, _) | (Some(1 | 2 | 5), Some(5)) => Ok(()),
+            (Some(mantissa), _) => Err(HyperliquidError::InvalidParameter(format!(
+                "mantissa {mantissa} needs nSigFigs 5 and must be 1, 2 or 5"
+            ))),
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct L2BookData {
     pub coin: String,
@@ -107,14 +152,228 @@ pub struct CandleData {
     pub volume: String,
 }
 
+impl CandleData {
+    pub fn open_f64(&self) -> Result<f64> {
+        parse_decimal(&self.open)
+    }
+
+    pub fn high_f64(&self) -> Result<f64> {
+        parse_decimal(&self.high)
+    }
+
+    pub fn low_f64(&self) -> Result<f64> {
+        parse_decimal(&self.low)
+    }
+
+    pub fn close_f64(&self) -> Result<f64> {
+        parse_decimal(&self.close)
+    }
+
+    pub fn volume_f64(&self) -> Result<f64> {
+        parse_decimal(&self.volume)
+    }
+}
+
+/// The candle intervals the venue supports.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CandleInterval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "3m")]
+    ThreeMinutes,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "15m")]
+    FifteenMinutes,
+    #[serde(rename = "30m")]
+    ThirtyMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "2h")]
+    TwoHours,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[serde(rename = "8h")]
+    EightHours,
+    #[serde(rename = "12h")]
+    TwelveHours,
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "3d")]
+    ThreeDays,
+    #[serde(rename = "1w")]
+    OneWeek,
+    #[serde(rename = "1M")]
+    OneMonth,
+}
+
+impl CandleInterval {
+    pub const ALL: [CandleInterval; 14] = [
+        CandleInterval::OneMinute,
+        CandleInterval::ThreeMinutes,
+        CandleInterval::FiveMinutes,
+        CandleInterval::FifteenMinutes,
+        CandleInterval::ThirtyMinutes,
+        CandleInterval::OneHour,
+        CandleInterval::TwoHours,
+        CandleInterval::FourHours,
+        CandleInterval::EightHours,
+        CandleInterval::TwelveHours,
+        CandleInterval::OneDay,
+        CandleInterval::ThreeDays,
+        CandleInterval::OneWeek,
+        CandleInterval::OneMonth,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CandleInterval::OneMinute => "1m",
+            CandleInterval::ThreeMinutes => "3m",
+            CandleInterval::FiveMinutes => "5m",
+            CandleInterval::FifteenMinutes => "15m",
+            CandleInterval::ThirtyMinutes => "30m",
+            CandleInterval::OneHour => "1h",
+            CandleInterval::TwoHours => "2h",
+            CandleInterval::FourHours => "4h",
+            CandleInterval::EightHours => "8h",
+            CandleInterval::TwelveHours => "12h",
+            CandleInterval::OneDay => "1d",
+            CandleInterval::ThreeDays => "3d",
+            CandleInterval::OneWeek => "1w",
+            CandleInterval::OneMonth => "1M",
+        }
+    }
+
+    /// Length of one candle in milliseconds. Months count as 31 days, the longest they can be.
+    pub fn duration_ms(&self) -> u64 {
+        const MINUTE: u64 = 60_000;
+        const HOUR: u64 = 60 * MINUTE;
+        const DAY: u64 = 24 * HOUR;
+        match self {
+            CandleInterval::OneMinute => MINUTE,
+            CandleInterval::ThreeMinutes => 3 * MINUTE,
+            CandleInterval::FiveMinutes => 5 * MINUTE,
+            CandleInterval::FifteenMinutes => 15 * MINUTE,
+            CandleInterval::ThirtyMinutes => 30 * MINUTE,
+            CandleInterval::OneHour => HOUR,
+            CandleInterval::TwoHours => 2 * HOUR,
+            CandleInterval::FourHours => 4 * HOUR,
+            CandleInterval::EightHours => 8 * HOUR,
+            CandleInterval::TwelveHours => 12 * HOUR,
+            CandleInterval::OneDay => DAY,
+            CandleInterval::ThreeDays => 3 * DAY,
+            CandleInterval::OneWeek => 7 * DAY,
+            CandleInterval::OneMonth => 31 * DAY,
+        }
+    }
+}
+
+impl fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = HyperliquidError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        CandleInterval::ALL
+            .into_iter()
+            .find(|interval| interval.as_str() == s)
+            .ok_or_else(|| HyperliquidError::InvalidParameter(format!("unknown candle interval {s}")))
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderUpdate {
     pub order: BasicOrder,
-    pub status: String,
+    pub status: OrderStatus,
     pub status_timestamp: u64,
 }
 
+/// Status of an order, as reported by `orderStatus`, `historicalOrders` and order updates.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Canceled,
+    /// A trigger order whose trigger price was reached; it now rests or fills as a normal order.
+    Triggered,
+    Rejected,
+    MarginCanceled,
+    VaultWithdrawalCanceled,
+    OpenInterestCapCanceled,
+    SelfTradeCanceled,
+    ReduceOnlyCanceled,
+    SiblingFilledCanceled,
+    DelistedCanceled,
+    LiquidatedCanceled,
+    ScheduledCancel,
+    TickRejected,
+    MinTradeNtlRejected,
+    PerpMarginRejected,
+    ReduceOnlyRejected,
+    BadAloPxRejected,
+    IocCancelRejected,
+    BadTriggerPxRejected,
+    MarketOrderNoLiquidityRejected,
+    PositionIncreaseAtOpenInterestCapRejected,
+    PositionFlipAtOpenInterestCapRejected,
+    TooAggressiveAtOpenInterestCapRejected,
+    OpenInterestIncreaseRejected,
+    InsufficientSpotBalanceRejected,
+    OracleRejected,
+    PerpMaxPositionRejected,
+    /// A status this version of the SDK doesn't know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl OrderStatus {
+    /// Whether the order was canceled, by the user or by the venue.
+    pub fn is_canceled(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Canceled
+                | OrderStatus::MarginCanceled
+                | OrderStatus::VaultWithdrawalCanceled
+                | OrderStatus::OpenInterestCapCanceled
+                | OrderStatus::SelfTradeCanceled
+                | OrderStatus::ReduceOnlyCanceled
+                | OrderStatus::SiblingFilledCanceled
+                | OrderStatus::DelistedCanceled
+                | OrderStatus::LiquidatedCanceled
+                | OrderStatus::ScheduledCancel
+        )
+    }
+
+    /// Whether the order was rejected when placed.
+    pub fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            OrderStatus::Rejected
+                | OrderStatus::TickRejected
+                | OrderStatus::MinTradeNtlRejected
+                | OrderStatus::PerpMarginRejected
+                | OrderStatus::ReduceOnlyRejected
+                | OrderStatus::BadAloPxRejected
+                | OrderStatus::IocCancelRejected
+                | OrderStatus::BadTriggerPxRejected
+                | OrderStatus::MarketOrderNoLiquidityRejected
+                | OrderStatus::PositionIncreaseAtOpenInterestCapRejected
+                | OrderStatus::PositionFlipAtOpenInterestCapRejected
+                | OrderStatus::TooAggressiveAtOpenInterestCapRejected
+                | OrderStatus::OpenInterestIncreaseRejected
+                | OrderStatus::InsufficientSpotBalanceRejected
+                | OrderStatus::OracleRejected
+                | OrderStatus::PerpMaxPositionRejected
+        )
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct BasicOrder {
@@ -312,6 +571,13 @@ pub struct PerpsAssetCtx {
     pub funding: String,
     pub open_interest: String,
     pub oracle_px: String,
+    #[serde(default)]
+    pub premium: Option<String>,
+    /// Prices at which a notional of the impact size would be bought and sold.
+    #[serde(default)]
+    pub impact_pxs: Option<Vec<String>>,
+    #[serde(default)]
+    pub day_base_vlm: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
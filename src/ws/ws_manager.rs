@@ -1,4 +1,5 @@
 use crate::errors::Error;
+use crate::ws::sub_structs::L2BookOptions;
 use crate::ws::message_types::{
     ActiveAssetCtx, AllMids, Candle, L2Book, Notification, OrderUpdates,
     Trades, User, UserFills, UserFundings, UserNonFundingLedgerUpdates, WebData2,
@@ -68,7 +69,14 @@ pub enum Subscription {
     Notification { user: Address },
     WebData2 { user: Address },
     Candle { coin: String, interval: String },
-    L2Book { coin: String },
+    /// Book updates of `coin`, aggregated as `options` asks. Updates don't say how they were
+    /// aggregated, so a coin can only be subscribed with one `options` at a time; subscribing
+    /// it again with different ones fails.
+    L2Book {
+        coin: String,
+        #[serde(flatten)]
+        options: L2BookOptions,
+    },
     Trades { coin: String },
     OrderUpdates { user: Address },
     UserEvents { user: Address },
@@ -173,9 +181,11 @@ impl WsManager {
                                                     );
                                                 }
                                             }
-                                        } else if let Err(err) =
-                                            Self::subscribe(writer_guard.deref_mut(), identifier)
-                                                .await
+                                        } else if let Err(err) = Self::subscribe(
+                                            writer_guard.deref_mut(),
+                                            v.first().map_or(identifier, |subscription_data| &subscription_data.id),
+                                        )
+                                        .await
                                         {
                                             error!("Could not resubscribe correctly {identifier}: {err}");
                                         }
@@ -255,6 +265,7 @@ impl WsManager {
             }
             Message::L2Book(l2_book) => serde_json::to_string(&Subscription::L2Book {
                 coin: l2_book.data.coin.clone(),
+                options: L2BookOptions::default(),
             })
             .map_err(|e| WsError::JsonParse(e.to_string())),
             Message::Candle(candle) => serde_json::to_string(&Subscription::Candle {
@@ -385,6 +396,29 @@ impl WsManager {
         Self::send_subscription_data("unsubscribe", writer, identifier).await
     }
 
+    /// The key subscriptions are stored under, which must match the identifier of their messages.
+    fn get_identifier_entry(identifier: &str) -> serde_json::Result<String> {
+        match serde_json::from_str::<Subscription>(identifier)? {
+            Subscription::UserEvents { user: _ } => Ok("userEvents".to_string()),
+            Subscription::OrderUpdates { user: _ } => Ok("orderUpdates".to_string()),
+            Subscription::L2Book { coin, options } if options != L2BookOptions::default() => {
+                serde_json::to_string(&Subscription::L2Book {
+                    coin,
+                    options: L2BookOptions::default(),
+                })
+            }
+            _ => Ok(identifier.to_string()),
+        }
+    }
+
+    /// The aggregation of an L2 book identifier, or `None` for any other subscription.
+    fn l2_book_options(identifier: &str) -> Option<L2BookOptions> {
+        match serde_json::from_str::<Subscription>(identifier) {
+            Ok(Subscription::L2Book { options, .. }) => Some(options),
+            _ => None,
+        }
+    }
+
     pub(crate) async fn add_subscription(
         &mut self,
         identifier: String,
@@ -392,19 +426,8 @@ impl WsManager {
     ) -> Result<u32> {
         let mut subscriptions = self.subscriptions_map.lock().await;
 
-        let identifier_entry = if let Subscription::UserEvents { user: _ } =
-            serde_json::from_str::<Subscription>(&identifier)
-                .map_err(|e| WsError::JsonParse(e.to_string()))?
-        {
-            "userEvents".to_string()
-        } else if let Subscription::OrderUpdates { user: _ } =
-            serde_json::from_str::<Subscription>(&identifier)
-                .map_err(|e| WsError::JsonParse(e.to_string()))?
-        {
-            "orderUpdates".to_string()
-        } else {
-            identifier.clone()
-        };
+        let identifier_entry =
+            Self::get_identifier_entry(&identifier).map_err(|e| WsError::JsonParse(e.to_string()))?;
         let subscriptions = subscriptions
             .entry(identifier_entry.clone())
             .or_insert(Vec::new());
@@ -413,6 +436,16 @@ impl WsManager {
             return Err(WsError::Subscription("User events already subscribed".to_string()));
         }
 
+        if let Some(first) = subscriptions.first() {
+            let options = Self::l2_book_options(&identifier);
+            if options.is_some() && options != Self::l2_book_options(&first.id) {
+                return Err(WsError::Subscription(format!(
+                    "L2 book already subscribed with other options: {}",
+                    first.id
+                )));
+            }
+        }
+
         if subscriptions.is_empty() {
             Self::subscribe(self.writer.lock().await.borrow_mut(), identifier.as_str()).await?;
         }
@@ -437,19 +470,8 @@ impl WsManager {
             .ok_or(Error::SubscriptionNotFound)?
             .clone();
 
-        let identifier_entry = if let Subscription::UserEvents { user: _ } =
-            serde_json::from_str::<Subscription>(&identifier)
-                .map_err(|e| Error::JsonParse(e.to_string()))?
-        {
-            "userEvents".to_string()
-        } else if let Subscription::OrderUpdates { user: _ } =
-            serde_json::from_str::<Subscription>(&identifier)
-                .map_err(|e| Error::JsonParse(e.to_string()))?
-        {
-            "orderUpdates".to_string()
-        } else {
-            identifier.clone()
-        };
+        let identifier_entry =
+            Self::get_identifier_entry(&identifier).map_err(|e| WsError::JsonParse(e.to_string()))?;
 
         self.subscription_identifiers.remove(&subscription_id);
 
@@ -462,10 +484,12 @@ impl WsManager {
             .iter()
             .position(|subscription_data| subscription_data.subscription_id == subscription_id)
             .ok_or(Error::SubscriptionNotFound)?;
+        // Unsubscribe with the identifier that was sent when subscribing.
+        let subscribed = subscriptions[0].id.clone();
         subscriptions.remove(index);
 
         if subscriptions.is_empty() {
-            Self::unsubscribe(self.writer.lock().await.borrow_mut(), identifier.as_str()).await?;
+            Self::unsubscribe(self.writer.lock().await.borrow_mut(), subscribed.as_str()).await?;
         }
         Ok(())
     }